@@ -36,11 +36,7 @@ pub fn bench<S: Strategy<N>, const N: usize>(
                     .into_iter()
                     .chain(possible_secrets.iter().cloned())
                     .collect();
-            GameState {
-                made_correct_guess: false,
-                possible_secrets,
-                allowed_guesses,
-            }
+            GameState::new(allowed_guesses, possible_secrets)
         };
 
         let routine = |state: &mut GameState<N>| strategy.make_guess(state);
@@ -26,12 +26,12 @@ pub fn bench<
 
     let routine = |state: &mut GameState<N>| {
         *state
-            .allowed_guesses
+            .allowed_guesses()
             .iter()
             .min_by_key(|guess| {
                 const ARR_SIZE: usize = 3usize.pow(N as u32);
                 let mut counts = [CounterType::zero(); ARR_SIZE];
-                state.possible_secrets.iter().for_each(|secret| {
+                state.possible_secrets().iter().copied().for_each(|secret| {
                     let clue = secret.compare_with_guess(**guess);
                     counts[clue.id()] += CounterType::one();
                 });
@@ -18,12 +18,13 @@ pub fn bench<const N: usize>(bencher: &mut Bencher, sizes: &(usize, usize)) {
 
     let routine = |state: &mut GameState<N>| {
         *state
-            .allowed_guesses
+            .allowed_guesses()
             .iter()
             .min_by_key(|guess| {
                 state
-                    .possible_secrets
+                    .possible_secrets()
                     .iter()
+                    .copied()
                     .map(|secret| {
                         let clue = secret.compare_with_guess(**guess);
                         clue.id()
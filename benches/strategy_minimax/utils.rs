@@ -44,10 +44,6 @@ impl GameStateGenerator {
             .chain(possible_secrets.iter().cloned())
             .collect();
 
-        GameState {
-            made_correct_guess: false,
-            allowed_guesses,
-            possible_secrets,
-        }
+        GameState::new(allowed_guesses, possible_secrets)
     }
 }
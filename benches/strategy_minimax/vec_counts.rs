@@ -22,12 +22,12 @@ pub fn bench<
 
     let routine = |state: &mut GameState<N>| {
         *state
-            .allowed_guesses
+            .allowed_guesses()
             .iter()
             .min_by_key(|guess| {
                 let arr_size: usize = 3usize.pow(N as u32);
                 let mut counts = vec![CounterType::zero(); arr_size];
-                state.possible_secrets.iter().for_each(|secret| {
+                state.possible_secrets().iter().copied().for_each(|secret| {
                     let clue = secret.compare_with_guess(**guess);
                     counts[clue.id()] += CounterType::one();
                 });
@@ -44,13 +44,15 @@ fn compare_words(secret_word: &str, guess: &str) -> Result<Vec<Tile>, Error> {
         .zip(guess.chars())
         .map(|(a, b)| a == b)
         .zip(result.iter_mut())
-        .filter_map(|(matching, loc)| matching.then(|| loc))
+        .filter(|(matching, _loc)| *matching)
+        .map(|(_matching, loc)| loc)
         .for_each(|loc| *loc = Tile::Correct);
 
     let mut counts = secret_word
         .chars()
         .zip(result.iter())
-        .filter_map(|(c, res)| (*res != Tile::Correct).then(|| c))
+        .filter(|(_c, res)| **res != Tile::Correct)
+        .map(|(c, _res)| c)
         .counts();
 
     guess
@@ -7,11 +7,252 @@ use structopt::StructOpt;
 
 use std::convert::TryInto;
 
-fn read_clue_from_stdin<const N: usize>() -> Result<Clue<N>, Error> {
+// Reads one line from stdin, returning None at the end of input.
+fn read_line_from_stdin() -> Option<String> {
     let mut line = "".to_string();
-    std::io::stdin().read_line(&mut line).unwrap();
-    let clue = line.trim().parse()?;
-    Ok(clue)
+    match std::io::stdin().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim().to_string()),
+    }
+}
+
+// Reads lines from stdin until one is accepted by `parse`, printing
+// the reason each rejected line was not accepted.  Returns None at the
+// end of input.
+fn read_until_valid<T>(
+    mut parse: impl FnMut(&str) -> Result<T, String>,
+) -> Option<T> {
+    loop {
+        let line = read_line_from_stdin()?;
+        match parse(&line) {
+            Ok(value) => return Some(value),
+            Err(reason) => println!("{}", reason),
+        }
+    }
+}
+
+// Describes why a line of input could not be parsed.
+fn describe_input_error<const N: usize>(err: Error) -> String {
+    match err {
+        Error::IncorrectStringLength => {
+            format!("Invalid input, expected {} letters or tiles", N)
+        }
+        Error::NotTileChar(c) => {
+            format!(
+                "Invalid input, '{}' is not a tile, use G/Y/_, g/y/b, \
+                 2/1/0, +/?/- or emoji",
+                c
+            )
+        }
+        Error::InvalidString(s) => format!("Invalid input, '{}'", s),
+        err => format!("Invalid input, {:?}", err),
+    }
+}
+
+// Checks that a clue could be received for a guess, and that it
+// leaves at least one possible secret.
+fn check_clue<const N: usize>(
+    game_state: &GameState<N>,
+    guess: Word<N>,
+    clue: Clue<N>,
+    notation: ClueNotation,
+) -> Result<(), String> {
+    let shown = clue.display_in(notation);
+    if !clue.is_possible_for(guess) {
+        Err(format!("Clue {} is impossible for guess {}", shown, guess))
+    } else if !game_state.after_guess(guess, clue).is_valid() {
+        Err(format!(
+            "Warning: no possible secrets give clue {} for guess {}, \
+             not applying it",
+            shown, guess
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+// Describes the corrections that would make a game's history
+// consistent, for the user to choose from.
+fn describe_corrections<const N: usize>(
+    turns: &[(Word<N>, Clue<N>)],
+    corrections: &[Vec<Correction<N>>],
+    notation: ClueNotation,
+) -> String {
+    corrections
+        .iter()
+        .take(5)
+        .map(|correction_set| {
+            let changes = correction_set
+                .iter()
+                .map(|correction| match *correction {
+                    Correction::Clue { turn, clue } => format!(
+                        "turn {}: {} {} -> {} {}",
+                        turn + 1,
+                        turns[turn].0,
+                        turns[turn].1.display_in(notation),
+                        turns[turn].0,
+                        clue.display_in(notation)
+                    ),
+                    Correction::Guess { turn, guess } => format!(
+                        "turn {}: {} {} -> {} {}",
+                        turn + 1,
+                        turns[turn].0,
+                        turns[turn].1.display_in(notation),
+                        guess,
+                        turns[turn].1.display_in(notation)
+                    ),
+                })
+                .join(", ");
+            format!("\n\tPossible correction: {}", changes)
+        })
+        .join("")
+}
+
+// Reads guesses from stdin until one is allowed by the game state, as
+// checked by `is_allowed`.  Returns None at the end of input.
+fn read_guess_from_stdin<const N: usize>(
+    is_allowed: impl Fn(&Word<N>) -> bool,
+    hard_mode: bool,
+) -> Option<Word<N>> {
+    loop {
+        let line = read_line_from_stdin()?;
+        match line.parse::<Word<N>>() {
+            Ok(guess) if is_allowed(&guess) => return Some(guess),
+            Ok(guess) if hard_mode => println!(
+                "{} is not an allowed guess, or does not use every hint",
+                guess
+            ),
+            Ok(guess) => println!("{} is not an allowed guess", guess),
+            Err(_) => println!("Guesses must be {} letters", N),
+        }
+    }
+}
+
+// Host a game for the user to guess.  If `secret` is None, the host
+// is adversarial and picks each clue to keep as many secrets
+// possible as it can.
+fn play_single<const N: usize>(
+    mut game_state: GameState<N>,
+    secret: Option<Word<N>>,
+    notation: ClueNotation,
+) -> Result<(), Error> {
+    let mut history = Vec::new();
+    while !game_state.is_finished() && !game_state.is_lost() {
+        println!("Guess {}:", game_state.num_guesses + 1);
+        let guess = match read_guess_from_stdin(
+            |guess| game_state.is_allowed_guess(guess),
+            game_state.hard_mode,
+        ) {
+            Some(guess) => guess,
+            None => break,
+        };
+        let clue = match secret {
+            Some(secret) => secret.compare_with_guess(guess),
+            None => game_state.adversarial_clue(&guess)?,
+        };
+        println!("{} {}", guess, clue.display_in(notation));
+        history.push(clue);
+        game_state = game_state.after_guess(guess, clue);
+    }
+
+    // A game abandoned partway through has no result worth sharing.
+    if game_state.is_finished() || game_state.is_lost() {
+        print_share_text(&game_state, &history);
+    }
+    if game_state.is_finished() {
+        println!("Solved in {} guesses", game_state.num_guesses);
+    } else if let Some(secret) = secret {
+        println!("The secret word was {}", secret);
+    } else {
+        println!(
+            "{} possible secret words remained",
+            game_state.num_possible_secrets()
+        );
+    }
+
+    Ok(())
+}
+
+fn play_multi<const N: usize, const GAMES: usize, R: rand::Rng>(
+    mut game_state: MultiGameState<N, GAMES>,
+    rng: &mut R,
+    notation: ClueNotation,
+) -> Result<(), Error> {
+    let secrets = game_state.random_secret(rng);
+    let hard_mode = game_state.games.iter().any(|game| game.hard_mode);
+    while !game_state.is_finished() && !game_state.is_lost() {
+        println!("Guess {}:", game_state.games[0].num_guesses + 1);
+        let guess = match read_guess_from_stdin(
+            |guess| game_state.is_allowed_guess(guess),
+            hard_mode,
+        ) {
+            Some(guess) => guess,
+            None => break,
+        };
+        let clues = secrets.map(|secret| secret.compare_with_guess(guess));
+        println!(
+            "{} {}",
+            guess,
+            game_state
+                .games
+                .iter()
+                .zip(clues.iter())
+                .map(|(game, clue)| if game.is_finished() {
+                    "done".to_string()
+                } else {
+                    clue.display_in(notation).to_string()
+                })
+                .join(" ")
+        );
+        game_state = game_state.after_guess(guess, clues);
+    }
+
+    if game_state.is_finished() {
+        println!("Solved in {} guesses", game_state.games[0].num_guesses);
+    } else {
+        println!(
+            "The secret words were {}",
+            secrets.iter().map(|secret| format!("{}", secret)).join(" ")
+        );
+    }
+
+    Ok(())
+}
+
+// A line of input while running interactively.
+enum Command<const N: usize> {
+    // The clue received, along with the word guessed if it differs
+    // from the suggestion.  Written as "_YG__" or "CRANE _YG__".
+    Turn(Option<Word<N>>, Clue<N>),
+
+    // Remove the most recent guess and clue.
+    Undo,
+
+    // Print the remaining possible secrets.
+    Show,
+
+    // Print how a word would split the remaining possible secrets.
+    // Written as "explain CRANE".
+    Explain(Word<N>),
+}
+
+impl<const N: usize> std::str::FromStr for Command<N> {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let tokens = s.split_whitespace().collect::<Vec<_>>();
+        match tokens.as_slice() {
+            [command] if command.eq_ignore_ascii_case("undo") => Ok(Self::Undo),
+            [command] if command.eq_ignore_ascii_case("show") => Ok(Self::Show),
+            [command, word] if command.eq_ignore_ascii_case("explain") => {
+                Ok(Self::Explain(word.parse()?))
+            }
+            [clue] => Ok(Self::Turn(None, clue.parse()?)),
+            [guess, clue] => {
+                Ok(Self::Turn(Some(guess.parse()?), clue.parse()?))
+            }
+            _ => Err(Error::InvalidString(s.to_string())),
+        }
+    }
 }
 
 fn run_multigame_interactively<
@@ -21,28 +262,45 @@ fn run_multigame_interactively<
 >(
     strategy: &S,
     mut game_state: MultiGameState<N, GAMES>,
+    notation: ClueNotation,
 ) -> Result<(), Error> {
     while !game_state.is_finished() {
+        if game_state.is_lost() {
+            println!("Out of guesses, failed to solve every puzzle");
+            return Ok(());
+        }
+        println!("Guess {}", game_state.games[0].num_guesses + 1);
         game_state.games.iter().enumerate().for_each(|(i, game)| {
             println!(
                 "Game {} has {} possibilities remaining",
                 i,
-                game.possible_secrets.len()
+                game.num_possible_secrets()
             )
         });
 
         let best_guess = strategy.make_guess(&game_state)?;
         println!("Best word to guess = {}", best_guess);
 
-        let clues: [Clue<N>; GAMES] = (0..GAMES)
-            .map(|_| read_clue_from_stdin())
-            .collect::<Result<Vec<_>, _>>()?
-            .try_into()
-            .unwrap();
+        let clues = game_state
+            .games
+            .iter()
+            .map(|game| {
+                read_until_valid(|line| {
+                    let clue =
+                        line.parse().map_err(describe_input_error::<N>)?;
+                    check_clue(game, best_guess, clue, notation)?;
+                    Ok(clue)
+                })
+            })
+            .collect::<Option<Vec<Clue<N>>>>();
+        let clues: [Clue<N>; GAMES] = match clues {
+            Some(clues) => clues.try_into().unwrap(),
+            None => return Ok(()),
+        };
 
         println!(
             "Clue received was {}",
-            clues.iter().map(|clue| format!("{}", clue)).join(" ")
+            clues.iter().map(|clue| clue.display_in(notation)).join(" ")
         );
         game_state = game_state.after_guess(best_guess, clues);
     }
@@ -53,73 +311,318 @@ fn run_multigame_interactively<
         game_state
             .games
             .iter()
-            .map(|game| format!("{}", game.possible_secrets[0]))
+            .map(|game| format!("{}", game.possible_secrets()[0]))
             .join(" ")
     );
 
     Ok(())
 }
 
+fn print_partition_report<const N: usize>(
+    report: &PartitionReport<N>,
+    notation: ClueNotation,
+) {
+    println!(
+        "{} splits the possible secrets into {} clues",
+        report.guess,
+        report.buckets.len()
+    );
+    println!("Worst case: {} secrets", report.worst_case);
+    println!("Expected: {:.3} secrets", report.expected);
+    println!("Entropy: {:.4} bits", report.entropy);
+    report.buckets.iter().for_each(|bucket| {
+        let ellipsis = if bucket.num_secrets > bucket.examples.len() {
+            ", ..."
+        } else {
+            ""
+        };
+        println!(
+            "\t{}  {:>5}  {}{}",
+            bucket.clue.display_in(notation),
+            bucket.num_secrets,
+            bucket.examples.iter().join(", "),
+            ellipsis
+        );
+    });
+}
+
+// Parses a turn written as GUESS:CLUE, such as "crane:_YG__".
+fn parse_history_entry<const N: usize>(
+    entry: &str,
+) -> Result<(Word<N>, Clue<N>), Error> {
+    let (guess, clue) = entry.split_once(':').ok_or_else(|| {
+        Error::InvalidHistory(format!("Expected GUESS:CLUE, found {}", entry))
+    })?;
+    Ok((guess.parse()?, clue.parse()?))
+}
+
+// Replays the guesses and clues received so far, then prints the
+// remaining secrets and the guess to make next.
+fn solve_from_history<S: Strategy<N>, const N: usize>(
+    strategy: &S,
+    initial_state: &GameState<N>,
+    turns: &[(Word<N>, Clue<N>)],
+    num_suggestions: usize,
+    notation: ClueNotation,
+) -> Result<(), Error> {
+    let game_state = turns.iter().enumerate().try_fold(
+        initial_state.clone(),
+        |state, (i, &(guess, clue))| {
+            let next_state = state.after_guess(guess, clue);
+            if clue.is_possible_for(guess) && next_state.is_valid() {
+                return Ok(next_state);
+            }
+            let corrections = initial_state.diagnose(&turns[..=i]);
+            println!(
+                "No possible secrets give clue {} for guess {}{}",
+                clue.display_in(notation),
+                guess,
+                describe_corrections(&turns[..=i], &corrections, notation)
+            );
+            Err(Error::InvalidHistory(format!(
+                "{}:{}",
+                guess,
+                clue.display_in(notation)
+            )))
+        },
+    )?;
+
+    if game_state.is_finished() {
+        println!("Already solved");
+        return Ok(());
+    }
+    println!(
+        "{} possibilities remaining",
+        game_state.num_possible_secrets()
+    );
+    game_state
+        .possible_secrets()
+        .iter()
+        .copied()
+        .take(50)
+        .for_each(|word| println!("\tPossible: {}", word));
+    if game_state.num_possible_secrets() > 50 {
+        println!("\t...");
+    }
+    if game_state.is_lost() {
+        println!("Out of guesses, failed to find the secret word");
+        return Ok(());
+    }
+    let guess = print_ranked_guesses(strategy, &game_state, num_suggestions)?;
+    println!("Best word to guess = {}", guess);
+    Ok(())
+}
+
+// Prints the text shared after a game, given the clues received.
+fn print_share_text<const N: usize>(state: &GameState<N>, clues: &[Clue<N>]) {
+    if clues.is_empty() {
+        return;
+    }
+    let share = ShareText::new(clues.to_vec())
+        .with_max_guesses(state.max_guesses.unwrap_or(clues.len()))
+        .with_hard_mode(state.hard_mode);
+    println!();
+    print!("{}", share);
+}
+
+// Prints the guesses ranked highest by a strategy, and returns the
+// best of them.
+fn print_ranked_guesses<S: Strategy<N>, const N: usize>(
+    strategy: &S,
+    state: &GameState<N>,
+    num_suggestions: usize,
+) -> Result<Word<N>, Error> {
+    let ranked = strategy.ranked_guesses(state, num_suggestions.max(1))?;
+    if num_suggestions > 1 {
+        println!("Top guesses:");
+        ranked.iter().enumerate().for_each(|(i, ranked)| {
+            let row = format!(
+                "\t{}. {}  {:<24}{}",
+                i + 1,
+                ranked.guess,
+                ranked.score,
+                if ranked.is_possible_secret {
+                    "possible secret"
+                } else {
+                    ""
+                }
+            );
+            println!("{}", row.trim_end())
+        });
+    }
+    ranked
+        .first()
+        .map(|ranked| ranked.guess)
+        .ok_or(Error::NoWordsRemaining)
+}
+
 fn run_interactively<S: Strategy<N>, const N: usize>(
     strategy: &S,
     mut game_state: GameState<N>,
+    num_suggestions: usize,
+    notation: ClueNotation,
 ) -> Result<(), Error> {
+    // The state before each turn, along with the guess and clue.
+    let mut history: Vec<(GameState<N>, Word<N>, Clue<N>)> = Vec::new();
+    let mut best_guess = None;
     while !game_state.is_finished() {
-        println!(
-            "{} possibilities remaining",
-            game_state.possible_secrets.len()
-        );
+        if game_state.is_lost() {
+            println!("Out of guesses, failed to find the secret word");
+            return Ok(());
+        }
 
-        let best_guess = strategy.make_guess(&game_state)?;
-        println!("Best word to guess = {}", best_guess);
+        let suggested = match best_guess {
+            Some(guess) => guess,
+            None => {
+                println!(
+                    "{} possibilities remaining",
+                    game_state.num_possible_secrets()
+                );
+                let guess = print_ranked_guesses(
+                    strategy,
+                    &game_state,
+                    num_suggestions,
+                )?;
+                println!("Best word to guess = {}", guess);
+                best_guess = Some(guess);
+                guess
+            }
+        };
 
-        let clue = read_clue_from_stdin()?;
+        let command = read_until_valid(|line| {
+            let command = line.parse().map_err(describe_input_error::<N>)?;
+            if let Command::Turn(guess, clue) = command {
+                let guess = guess.unwrap_or(suggested);
+                check_clue(&game_state, guess, clue, notation).map_err(
+                    |reason| {
+                        let initial_state =
+                            history.first().map_or(&game_state, |turn| &turn.0);
+                        let turns = history
+                            .iter()
+                            .map(|(_state, guess, clue)| (*guess, *clue))
+                            .chain(std::iter::once((guess, clue)))
+                            .collect::<Vec<_>>();
+                        let corrections = initial_state.diagnose(&turns);
+                        let hint = if corrections.is_empty() {
+                            ""
+                        } else {
+                            "\n\tUse undo to correct an earlier turn"
+                        };
+                        format!(
+                            "{}{}{}",
+                            reason,
+                            describe_corrections(
+                                &turns,
+                                &corrections,
+                                notation
+                            ),
+                            hint
+                        )
+                    },
+                )?;
+            }
+            Ok(command)
+        });
+        let command = match command {
+            Some(command) => command,
+            None => return Ok(()),
+        };
 
-        println!("Clue received was {}", clue);
-        game_state = game_state.after_guess(best_guess, clue);
+        match command {
+            Command::Turn(guess, clue) => {
+                let guess = guess.unwrap_or(suggested);
+                println!(
+                    "Clue received for {} was {}",
+                    guess,
+                    clue.display_in(notation)
+                );
+                history.push((game_state.clone(), guess, clue));
+                game_state = game_state.after_guess(guess, clue);
+                best_guess = None;
+            }
+            Command::Undo => match history.pop() {
+                Some((prev, _guess, _clue)) => {
+                    println!("Undid the last guess");
+                    game_state = prev;
+                    best_guess = None;
+                }
+                None => println!("No guesses to undo"),
+            },
+            Command::Show => game_state
+                .possible_secrets()
+                .iter()
+                .copied()
+                .for_each(|word| println!("\tPossible: {}", word)),
+            Command::Explain(word) => {
+                print_partition_report(&game_state.explain(&word), notation)
+            }
+        }
     }
 
-    assert_eq!(game_state.possible_secrets.len(), 1);
-    println!("Winning word is {}", game_state.possible_secrets[0]);
+    assert_eq!(game_state.num_possible_secrets(), 1);
+    println!("Winning word is {}", game_state.possible_secrets()[0]);
+    let clues = history
+        .iter()
+        .map(|(_state, _guess, clue)| *clue)
+        .collect::<Vec<_>>();
+    print_share_text(&game_state, &clues);
 
     Ok(())
 }
 
-fn simulate_strategy<S: Strategy<N>, const N: usize>(
-    game_state: &GameState<N>,
+fn print_simulation<S: Strategy<N>, const N: usize>(
+    results: impl Iterator<
+        Item = Result<(Option<(Word<N>, Clue<N>)>, GameState<N>), Error>,
+    >,
     strategy: &S,
-    secret_word: Word<N>,
+    num_suggestions: usize,
+    notation: ClueNotation,
 ) {
-    game_state
-        .simulate_strategy(secret_word, strategy)
-        .for_each(|res_state| {
-            match &res_state {
-                Ok((Some((guess, clue)), _)) => {
-                    println!("Guessed: {}", guess);
-                    println!("Clue: {}", clue);
-                }
-                _ => (),
+    let mut clues = Vec::new();
+    let mut final_state = None;
+    results.for_each(|res_state| {
+            let to_rank = match &res_state {
+                Ok((_, state)) if !state.is_lost() && state.num_possible_secrets() > 1 => Some(state.clone()),
+                _ => None,
+            };
+            if let Ok((Some((guess, clue)), state)) = &res_state {
+                clues.push(*clue);
+                final_state = Some(state.clone());
+                println!("Guessed: {}", guess);
+                println!("Clue: {}", clue.display_in(notation));
             }
             match res_state {
                 Ok((_, state)) if !state.is_valid() => {
                     println!("Strategy failed, erroneously eliminated all possibilities.");
                 }
-                Ok((_, state)) if state.possible_secrets.len() == 1 => {
-                    println!("Winner, discovered secret word {}", state.possible_secrets[0]);
+                Ok((_, state)) if state.is_lost() => {
+                    println!("Strategy failed to find the secret word within {} guesses", state.num_guesses);
                 }
-                Ok((_, state)) if state.possible_secrets.len() < 15 => {
-                    println!("{} possible secret words remaining", state.possible_secrets.len());
+                Ok((_, state)) if state.num_possible_secrets() == 1 => {
+                    println!("Winner, discovered secret word {}", state.possible_secrets()[0]);
+                }
+                Ok((_, state)) if state.num_possible_secrets() < 15 => {
+                    println!("{} possible secret words remaining", state.num_possible_secrets());
                     state
-                        .possible_secrets
-                        .iter()
+                        .possible_secrets().iter().copied()
                         .for_each(|word| println!("\tPossible: {}", word))
                 }
                 Ok((_, state)) => {
-                    println!("{} possible secret words remaining", state.possible_secrets.len());
+                    println!("{} possible secret words remaining", state.num_possible_secrets());
                 }
                 Err(e) => println!("Error: {:?}", e),
             }
+            if let Some(state) = to_rank.filter(|_| num_suggestions > 1) {
+                if let Err(e) = print_ranked_guesses(strategy, &state, num_suggestions) {
+                    println!("Error: {:?}", e);
+                }
+            }
         });
+    if let Some(state) =
+        final_state.filter(|state| state.is_finished() || state.is_lost())
+    {
+        print_share_text(&state, &clues);
+    }
 }
 
 fn simulate_multi_strategy<
@@ -130,27 +633,28 @@ fn simulate_multi_strategy<
     game_state: &MultiGameState<N, GAMES>,
     strategy: &S,
     secret_words: [Word<N>; GAMES],
+    notation: ClueNotation,
 ) {
     game_state
         .simulate_strategy(secret_words, strategy)
         .enumerate()
         .for_each(|(i,res_state)| {
-            match &res_state {
-                Ok((Some((guess, clue)), _)) => {
-                    println!("Guess #{}: {}", i, guess);
-                    println!("Clue #{}: [{}]", i, clue.iter().map(|c| format!("{}",c)).join(", "));
-                }
-                _ => (),
+            if let Ok((Some((guess, clue)), _)) = &res_state {
+                println!("Guess #{}: {}", i, guess);
+                println!("Clue #{}: [{}]", i, clue.iter().map(|c| c.display_in(notation)).join(", "));
             }
             match res_state {
                 Ok((_, state)) if !state.is_valid() => {
                     println!("Strategy failed, erroneously eliminated all possibilities.");
                 }
+                Ok((_, state)) if state.is_lost() => {
+                    println!("Strategy failed to solve every puzzle within {} guesses", i);
+                }
                 Ok((_, state)) if state.is_finished() => {
                     println!("Winner, discovered secret words [{}]",
                              state.games
                                   .iter()
-                                  .map(|game| game.possible_secrets[0])
+                                  .map(|game| game.possible_secrets()[0])
                                   .map(|word| format!("{}",word))
                                   .join(", ")
                     );
@@ -160,13 +664,13 @@ fn simulate_multi_strategy<
                              state.games
                                   .iter()
                              .map(|game| {
-                                 if game.possible_secrets.len()==1 {
+                                 if game.num_possible_secrets()==1 {
                                      let done_str = if game.is_finished() { " (done)" }  else {""};
-                                     format!("{}{}", game.possible_secrets[0],done_str)
-                                 } else if game.possible_secrets.len() < 3 {
-                                     game.possible_secrets.iter().map(|w| format!("{w}")).join("/")
+                                     format!("{}{}", game.possible_secrets()[0],done_str)
+                                 } else if game.num_possible_secrets() < 3 {
+                                     game.possible_secrets().iter().copied().map(|w| format!("{w}")).join("/")
                                  } else {
-                                     format!("{}", game.possible_secrets.len())
+                                     format!("{}", game.num_possible_secrets())
                                  }
                              })
                              .join(", ")
@@ -179,76 +683,372 @@ fn simulate_multi_strategy<
 
 #[derive(StructOpt)]
 struct Options {
+    /// Suggest a guess each turn, and read the clue received from
+    /// stdin.
     #[structopt(short = "i", long = "interactive")]
     interactive: bool,
 
+    /// The secret word used by --simulate, given once for each board.
+    /// With a single board, a random secret is chosen if none is given.
     #[structopt(short = "w", long = "secret-word")]
     secret_word: Vec<String>,
 
+    /// Play the strategy against a secret word, printing each guess and
+    /// the clue received.
     #[structopt(short = "s", long = "simulate")]
     simulate: bool,
 
+    /// Word list to use, either "wordle", "scrabble", or the path to a
+    /// file with one word per line.
     #[structopt(long = "allowed-word-list", default_value = "wordle")]
     word_list: String,
 
+    /// Only words of this length are read from the word list.
+    /// Supported lengths are 3 through 11.
+    #[structopt(long = "word-length", default_value = "5")]
+    word_length: usize,
+
+    /// Name of the strategy to use.  With --analysis, every strategy
+    /// listed is compared, or each default strategy if none are given.
     #[structopt(long = "strategy")]
     strategy: Vec<String>,
 
+    /// Compare strategies by the number of guesses each needs to solve
+    /// every possible secret.
     #[structopt(long = "analysis")]
     analysis: bool,
 
+    /// Every revealed hint must be used in later guesses.
+    #[structopt(long = "hard-mode")]
+    hard_mode: bool,
+
+    /// The game is lost if not solved within this many guesses.
+    /// Defaults to 6 for a single board, or the usual limit for the
+    /// number of boards.
+    #[structopt(long = "max-guesses")]
+    max_guesses: Option<usize>,
+
+    /// File giving the relative frequency of each possible secret, with
+    /// a word and its frequency on each line.
+    #[structopt(long = "word-frequencies")]
+    word_frequencies: Option<String>,
+
+    /// Write the decision tree of the strategy to this file, giving the
+    /// guesses and clues for one secret on each line.
+    #[structopt(long = "export-tree")]
+    export_tree: Option<String>,
+
+    /// Load a decision tree from this file, verify it against the word
+    /// list, and use it as the strategy.
+    #[structopt(long = "import-tree")]
+    import_tree: Option<String>,
+
+    /// Check that the decision tree in this file solves every possible
+    /// secret.
+    #[structopt(long = "verify-tree")]
+    verify_tree: Option<String>,
+
+    /// Run the exact worst-case search, without the candidate limit
+    /// used by the BoundedWorstCase strategy.  Slow for large word
+    /// lists.
+    #[structopt(long = "prove-worst-case")]
+    prove_worst_case: bool,
+
+    /// With --prove-worst-case, write the decision tree proving the
+    /// optimum to this file.
+    #[structopt(long = "proof-tree")]
+    proof_tree: Option<String>,
+
+    /// Play four boards at once, as in Quordle.
     #[structopt(long = "quordle")]
     quordle: bool,
 
+    /// Number of boards played simultaneously.  Supported counts are 1,
+    /// 2 (Dordle), 4 (Quordle), 8 (Octordle), 16 (Sedecordle) and 32
+    /// (Duotrigordle).  Defaults to 4 with --quordle, otherwise 1.
+    #[structopt(long = "boards")]
+    boards: Option<usize>,
+
+    /// Print the estimated difficulty of a multi-board puzzle with
+    /// these secret words.
     #[structopt(long = "quordle-difficulty")]
     quordle_difficulty: Option<Vec<String>>,
 
+    /// Print the estimated difficulty of this many multi-board
+    /// puzzles with random secret words.
     #[structopt(long = "quordle-difficulty-n-sim")]
     quordle_difficulty_n_sim: Option<usize>,
 
+    /// Seed used to choose the random secret words for
+    /// --quordle-difficulty-n-sim.
     #[structopt(long = "quordle-difficulty-sim-seed", default_value = "0")]
     quordle_difficulty_sim_seed: u64,
+
+    /// Play as the guesser against a randomly chosen secret.
+    #[structopt(long = "play")]
+    play: bool,
+
+    /// Use an adversarial host that never fixes the secret, as in
+    /// Absurdle, for --play and --simulate.
+    #[structopt(long = "absurdle")]
+    absurdle: bool,
+
+    /// Number of random secrets used by --analysis with multiple
+    /// boards.
+    #[structopt(long = "num-samples", default_value = "100")]
+    num_samples: usize,
+
+    /// Seed used to choose the random secrets for --analysis with
+    /// multiple boards, and the secret for --play.  Defaults to 0 for
+    /// --analysis, and to a new random seed for each --play game.
+    #[structopt(long = "seed")]
+    seed: Option<u64>,
+
+    /// Number of ranked guesses shown with their scores before each
+    /// guess in --interactive and --simulate.
+    #[structopt(long = "num-suggestions", default_value = "5")]
+    num_suggestions: usize,
+
+    /// Print how each of these words would split the possible secrets
+    /// as a first guess.
+    #[structopt(long = "explain")]
+    explain: Vec<String>,
+
+    /// Read a shared result, as pasted from the game, and print the
+    /// clues received on each turn.
+    #[structopt(long = "import-share")]
+    import_share: Option<String>,
+
+    /// Notation used to print clues, one of letters (G/Y/_), lowercase
+    /// (g/y/b), digits (2/1/0), symbols (+/?/-) or emoji.  Clues are
+    /// read in any of these notations.
+    #[structopt(long = "clue-notation", default_value = "letters")]
+    clue_notation: String,
+
+    /// Guesses already made and the clues received, written as
+    /// GUESS:CLUE.  Prints the remaining secrets and the best next
+    /// guess, then exits.
+    #[structopt(long = "history")]
+    history: Vec<String>,
+
+    /// As --history, reading whitespace-separated GUESS:CLUE pairs
+    /// from a file.
+    #[structopt(long = "history-file")]
+    history_file: Option<String>,
 }
 
-fn run_single(game_state: GameState<5>, opt: &Options) -> Result<(), Error> {
-    let strategy = opt
-        .strategy
-        .first()
-        .map(|name| {
-            strategy::all_strategies()
-                .remove(name)
-                .unwrap_or_else(|| panic!("Unknown strategy: {}", name))
+impl Options {
+    // The number of boards played, as given by --boards, or implied
+    // by the quordle options.
+    fn num_boards(&self) -> usize {
+        let is_quordle = self.quordle
+            || self.quordle_difficulty.is_some()
+            || self.quordle_difficulty_n_sim.is_some();
+        match self.boards {
+            Some(boards) => boards,
+            None if is_quordle => 4,
+            None => 1,
+        }
+    }
+}
+
+// The random number generator used to pick secrets for --play.  Unless
+// a seed is given, a new one is chosen each game, and printed so that
+// the game can be replayed.
+fn play_rng(opt: &Options) -> rand_chacha::ChaCha8Rng {
+    let seed = opt.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    println!("Playing with --seed {}", seed);
+    rand_chacha::ChaCha8Rng::seed_from_u64(seed)
+}
+
+fn run_single<const N: usize>(
+    game_state: GameState<N>,
+    opt: &Options,
+) -> Result<(), Error> {
+    let notation: ClueNotation = opt.clue_notation.parse()?;
+    let imported_tree = opt
+        .import_tree
+        .as_ref()
+        .map(|path| -> Result<DecisionTree<N>, Error> {
+            let tree = DecisionTree::from_file(path)?;
+            tree.verify(&game_state)?;
+            Ok(tree)
         })
-        .unwrap_or_else(|| Box::new(strategy::MiniMax));
+        .transpose()?;
+
+    let strategy: Box<dyn Strategy<N>> = match &imported_tree {
+        Some(tree) => Box::new(tree.clone()),
+        None => opt
+            .strategy
+            .first()
+            .map(|name| {
+                strategy::named_strategies()
+                    .remove(name)
+                    .unwrap_or_else(|| panic!("Unknown strategy: {}", name))
+            })
+            .unwrap_or_else(|| Box::new(strategy::MiniMax)),
+    };
+
+    if let Some(path) = &opt.verify_tree {
+        let verified = DecisionTree::<N>::from_file(path).and_then(|tree| {
+            tree.verify(&game_state)?;
+            Ok(tree)
+        });
+        match verified {
+            Ok(tree) => {
+                println!("Decision tree in {} is valid", path);
+                println!("Mean guesses: {}", tree.mean_guesses());
+                println!("Max guesses: {}", tree.depth());
+            }
+            Err(e) => {
+                println!("Decision tree in {} is invalid", path);
+                return Err(e);
+            }
+        }
+    }
+
+    if opt.prove_worst_case {
+        let solver = strategy::OptimalWorstCase::new()
+            .with_max_depth(game_state.max_guesses.unwrap_or(6));
+        match solver.worst_case(&game_state) {
+            Some(depth) => {
+                println!("Optimal worst case: {} guesses", depth);
+                let tree = solver.proof_tree(&game_state)?;
+                tree.verify(&game_state)?;
+                if let Some(path) = &opt.proof_tree {
+                    tree.write_to_file(path)?;
+                    println!("Wrote proof tree to {}", path);
+                }
+            }
+            None => println!(
+                "No strategy solves every secret within {} guesses",
+                game_state.max_guesses.unwrap_or(6)
+            ),
+        }
+    }
+
+    if let Some(path) = &opt.export_tree {
+        let tree = strategy.decision_tree(&game_state)?;
+        tree.write_to_file(path)?;
+        println!(
+            "Wrote decision tree for {} secrets to {}",
+            tree.num_leaves(),
+            path
+        );
+    }
+
+    if let Some(path) = &opt.import_share {
+        let text =
+            std::fs::read_to_string(path).map_err(Error::ShareFileError)?;
+        let share: ShareText<N> = text.parse()?;
+        println!(
+            "Imported {} clues, {}",
+            share.clues.len(),
+            if share.is_solved() {
+                "solved"
+            } else {
+                "unsolved"
+            }
+        );
+        share.clues.iter().enumerate().for_each(|(i, clue)| {
+            println!("Turn {}: {}", i + 1, clue.display_in(notation))
+        });
+    }
+
+    opt.explain
+        .iter()
+        .try_for_each(|word| -> Result<(), Error> {
+            print_partition_report(
+                &game_state.explain(&word.parse()?),
+                notation,
+            );
+            Ok(())
+        })?;
+
+    if !opt.history.is_empty() || opt.history_file.is_some() {
+        let from_file = opt
+            .history_file
+            .as_ref()
+            .map(std::fs::read_to_string)
+            .transpose()
+            .map_err(Error::HistoryFileError)?
+            .unwrap_or_default();
+        let turns = from_file
+            .split_whitespace()
+            .chain(opt.history.iter().map(|entry| entry.as_str()))
+            .map(parse_history_entry)
+            .collect::<Result<Vec<_>, Error>>()?;
+        return solve_from_history(
+            &strategy,
+            &game_state,
+            &turns,
+            opt.num_suggestions,
+            notation,
+        );
+    }
+
+    if opt.play {
+        let secret = (!opt.absurdle)
+            .then(|| game_state.random_secret(&mut play_rng(opt)));
+        play_single(game_state.clone(), secret, notation)?;
+    }
 
     if opt.interactive {
-        run_interactively(&strategy, game_state.clone())?;
+        run_interactively(
+            &strategy,
+            game_state.clone(),
+            opt.num_suggestions,
+            notation,
+        )?;
     }
 
-    if opt.simulate {
-        let secret_word: Word<5> = opt
+    if opt.simulate && opt.absurdle {
+        let results = game_state
+            .simulate_against_adversary(&strategy)
+            .collect::<Vec<_>>();
+        let num_guesses = match results.last() {
+            Some(Ok((_, state))) => Some(state.num_guesses),
+            _ => None,
+        };
+        print_simulation(
+            results.into_iter(),
+            &strategy,
+            opt.num_suggestions,
+            notation,
+        );
+        if let Some(num_guesses) = num_guesses {
+            println!("Guesses needed against the adversary: {}", num_guesses);
+        }
+        let tree = strategy.decision_tree(&game_state)?;
+        println!("Worst case over all secrets: {} guesses", tree.depth());
+    } else if opt.simulate {
+        let secret_word: Word<N> = opt
             .secret_word
             .first()
             .map(|s| s.parse())
             .transpose()?
             .unwrap_or_else(|| {
-                game_state.possible_secrets[rand::thread_rng()
-                    .gen_range(0..game_state.possible_secrets.len())]
+                game_state.random_secret(&mut rand::thread_rng())
             });
-        simulate_strategy(&game_state, &strategy, secret_word);
+        print_simulation(
+            game_state.simulate_strategy(secret_word, &strategy),
+            &strategy,
+            opt.num_suggestions,
+            notation,
+        );
     }
 
     if opt.analysis {
         let mut plotter = plots::WordlePlotter::new();
 
-        let strategies: Vec<(String, Box<dyn Strategy<5>>)> =
-            if opt.strategy.is_empty() {
+        let mut strategies: Vec<(String, Box<dyn Strategy<N>>)> =
+            if opt.strategy.is_empty() && imported_tree.is_none() {
                 strategy::all_strategies()
                     .into_iter()
                     .sorted_by_key(|(name, _strategy)| name.clone())
                     .collect()
             } else {
-                let mut strategy_map = strategy::all_strategies();
+                let mut strategy_map = strategy::named_strategies();
                 opt.strategy
                     .iter()
                     .cloned()
@@ -262,24 +1062,55 @@ fn run_single(game_state: GameState<5>, opt: &Options) -> Result<(), Error> {
                     .collect()
             };
 
-        strategies.into_iter().for_each(|(name, strategy)| {
-            println!("Running strategy '{}'", name);
-            let paths =
-                strategy.deterministic_strategy_results(game_state.clone());
-            let mean_guesses = (paths.iter().map(|p| p.len()).sum::<usize>()
-                as f32)
-                / (paths.len() as f32);
-            println!("Mean guesses: {}", mean_guesses);
-
-            let by_num_guesses = paths.iter().into_group_map_by(|p| p.len());
-            by_num_guesses
-                .iter()
-                .sorted_by_key(|(num, _paths)| *num)
-                .for_each(|(num, paths)| {
-                    println!("{} guesses to solve {} words", num, paths.len())
-                });
-            plotter.add_results(&name, &paths);
-        });
+        if let (Some(path), Some(tree)) = (&opt.import_tree, imported_tree) {
+            strategies.push((path.clone(), Box::new(tree)));
+        }
+
+        strategies.into_iter().try_for_each(
+            |(name, strategy)| -> Result<(), Error> {
+                println!("Running strategy '{}'", name);
+                let tree = strategy.decision_tree(&game_state)?;
+                println!("Mean guesses: {}", tree.mean_guesses());
+                if game_state.is_weighted() {
+                    println!(
+                        "Weighted mean guesses: {}",
+                        tree.weighted_mean_guesses(&game_state)
+                    );
+                }
+                println!("Max guesses: {}", tree.depth());
+
+                let max_guesses = game_state.max_guesses.unwrap_or(6);
+                println!(
+                    "Win rate within {} guesses: {:.2}%",
+                    max_guesses,
+                    100.0 * tree.win_rate(max_guesses)
+                );
+                let failures = tree.failures(max_guesses);
+                if !failures.is_empty() {
+                    println!(
+                        "Fails on {} words: {}",
+                        failures.len(),
+                        failures.iter().join(", ")
+                    );
+                }
+
+                let paths = tree.paths();
+                let by_num_guesses =
+                    paths.iter().into_group_map_by(|p| p.len());
+                by_num_guesses
+                    .iter()
+                    .sorted_by_key(|(num, _paths)| *num)
+                    .for_each(|(num, paths)| {
+                        println!(
+                            "{} guesses to solve {} words",
+                            num,
+                            paths.len()
+                        )
+                    });
+                plotter.add_results(&name, &tree);
+                Ok(())
+            },
+        )?;
 
         plotter.plot();
     }
@@ -287,10 +1118,49 @@ fn run_single(game_state: GameState<5>, opt: &Options) -> Result<(), Error> {
     Ok(())
 }
 
-fn run_quordle(
-    game_state: MultiGameState<5, 4>,
+fn run_multi<const N: usize, const GAMES: usize>(
+    game_state: GameState<N>,
     opt: &Options,
 ) -> Result<(), Error> {
+    let notation: ClueNotation = opt.clue_notation.parse()?;
+    let max_guesses = opt
+        .max_guesses
+        .unwrap_or_else(MultiGameState::<N, GAMES>::default_max_guesses);
+    let game_state = MultiGameState::<N, GAMES>::new(game_state)
+        .with_max_guesses(max_guesses);
+
+    if let Some(words) = &opt.quordle_difficulty {
+        let secret_words = words
+            .iter()
+            .map(|s| -> Result<Word<N>, Error> { s.parse() })
+            .collect::<Result<Vec<_>, _>>()?
+            .as_slice()
+            .try_into()
+            .map_err(|_| Error::IncorrectNumberOfWords)?;
+        let difficulty = game_state.estimate_difficulty(secret_words);
+        println!("Difficulty: {}", difficulty);
+        println!("Log10(Difficulty): {}", (difficulty as f64).log10());
+        return Ok(());
+    } else if let Some(n_sim) = opt.quordle_difficulty_n_sim {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(
+            opt.quordle_difficulty_sim_seed,
+        );
+        (0..n_sim)
+            .map(|_i| {
+                let secret = game_state.random_secret(&mut rng);
+                let diff = game_state.estimate_difficulty(secret);
+                (secret, diff)
+            })
+            .for_each(|(secret, diff)| {
+                println!(
+                    "{} {}",
+                    diff,
+                    secret.iter().map(|w| format!("{}", w)).join(" ")
+                );
+            });
+        return Ok(());
+    }
+
     let strategy = opt
         .strategy
         .first()
@@ -312,75 +1182,177 @@ fn run_quordle(
             Box::new(strategy::MultiSequential::new(strategy::MiniMax))
         });
 
+    if opt.play {
+        let mut rng = play_rng(opt);
+        play_multi(game_state.clone(), &mut rng, notation)?;
+    }
+
     if opt.interactive {
-        run_multigame_interactively(&strategy, game_state.clone())?;
+        run_multigame_interactively(&strategy, game_state.clone(), notation)?;
     }
 
     if opt.simulate {
-        let secret_words: [Word<5>; 4] = opt
+        let secret_words: [Word<N>; GAMES] = opt
             .secret_word
             .iter()
             .map(|s| s.parse())
-            .collect::<Result<Vec<Word<5>>, _>>()?
+            .collect::<Result<Vec<Word<N>>, _>>()?
             .as_slice()
             .try_into()
             .map_err(|_| Error::IncorrectNumberOfWords)?;
-        simulate_multi_strategy(&game_state, &strategy, secret_words);
+        simulate_multi_strategy(&game_state, &strategy, secret_words, notation);
     }
 
     if opt.analysis {
-        panic!("Analysis not implemented for quordle");
+        let mut rng =
+            rand_chacha::ChaCha8Rng::seed_from_u64(opt.seed.unwrap_or(0));
+        let secrets = (0..opt.num_samples)
+            .map(|_i| game_state.random_secret(&mut rng))
+            .collect::<Vec<_>>();
+
+        let strategies: Vec<(String, Box<dyn MultiStrategy<N, GAMES>>)> =
+            if opt.strategy.is_empty() {
+                strategy::all_multi_strategies()
+                    .into_iter()
+                    .sorted_by_key(|(name, _strategy)| name.clone())
+                    .collect()
+            } else {
+                let mut strategy_map = strategy::all_multi_strategies();
+                opt.strategy
+                    .iter()
+                    .cloned()
+                    .map(|name| {
+                        let strategy =
+                            strategy_map.remove(&name).unwrap_or_else(|| {
+                                panic!("Unknown or repeated strategy: {}", name)
+                            });
+                        (name, strategy)
+                    })
+                    .collect()
+            };
+
+        let mut plotter = plots::WordlePlotter::new();
+        let summaries = strategies
+            .iter()
+            .map(|(name, strategy)| -> Result<_, Error> {
+                println!("Running strategy '{}'", name);
+                let results = secrets
+                    .iter()
+                    .map(|secret| {
+                        let state = game_state
+                            .simulate_strategy(*secret, strategy)
+                            .last()
+                            .unwrap()?
+                            .1;
+                        Ok((state.games[0].num_guesses, state.is_lost()))
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                let guess_counts = results
+                    .iter()
+                    .map(|(num_guesses, _is_lost)| *num_guesses)
+                    .collect::<Vec<_>>();
+                let mean = guess_counts.iter().sum::<usize>() as f64
+                    / guess_counts.len() as f64;
+                let worst = guess_counts.iter().copied().max().unwrap_or(0);
+                let num_lost =
+                    results.iter().filter(|(_num, is_lost)| *is_lost).count();
+                let win_rate = 1.0 - (num_lost as f64) / (results.len() as f64);
+
+                println!("Mean guesses: {}", mean);
+                println!("Max guesses: {}", worst);
+                println!(
+                    "Win rate within {} guesses: {:.2}%",
+                    max_guesses,
+                    100.0 * win_rate
+                );
+                guess_counts.iter().counts().into_iter().sorted().for_each(
+                    |(num, count)| {
+                        println!("{} guesses to solve {} games", num, count)
+                    },
+                );
+                plotter.add_guess_counts(name, guess_counts.into_iter());
+
+                Ok((name, mean, worst, win_rate))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        println!();
+        println!(
+            "{:<45} {:>8} {:>6} {:>9}",
+            "Strategy", "Mean", "Max", "Win rate"
+        );
+        summaries
+            .iter()
+            .sorted_by(|a, b| a.1.total_cmp(&b.1))
+            .for_each(|(name, mean, worst, win_rate)| {
+                println!(
+                    "{:<45} {:>8.4} {:>6} {:>8.2}%",
+                    name,
+                    mean,
+                    worst,
+                    100.0 * win_rate
+                )
+            });
+
+        plotter.plot();
     }
 
     Ok(())
 }
 
-fn main() -> Result<(), Error> {
-    let opt = Options::from_args();
-
+fn run<const N: usize>(opt: Options) -> Result<(), Error> {
     let game_state = if opt.word_list == "wordle" {
-        GameState::<5>::from_wordle()
+        GameState::<N>::from_wordle()
     } else if opt.word_list == "scrabble" {
-        GameState::<5>::from_scrabble()
+        GameState::<N>::from_scrabble()
     } else {
-        GameState::<5>::from_files(&opt.word_list, &opt.word_list)?
+        GameState::<N>::from_files(&opt.word_list, &opt.word_list)?
+    }
+    .with_clue_table()
+    .with_hard_mode(opt.hard_mode);
+
+    let game_state = match &opt.word_frequencies {
+        Some(path) => game_state.with_weights_from_file(path)?,
+        None => game_state,
     };
 
-    if let Some(words) = opt.quordle_difficulty {
-        let game_state = MultiGameState::<5, 4>::new(game_state);
-        let secret_words = words
-            .into_iter()
-            .map(|s: String| -> Result<Word<5>, Error> { s.parse() })
-            .collect::<Result<Vec<_>, _>>()?
-            .as_slice()
-            .try_into()
-            .map_err(|_| Error::IncorrectNumberOfWords)?;
-        let difficulty = game_state.estimate_difficulty(secret_words);
-        println!("Difficulty: {}", difficulty);
-        println!("Log10(Difficulty): {}", (difficulty as f64).log10());
-    } else if let Some(n_sim) = opt.quordle_difficulty_n_sim {
-        let game_state = MultiGameState::<5, 4>::new(game_state);
-        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(
-            opt.quordle_difficulty_sim_seed,
-        );
-        (0..n_sim)
-            .map(|_i| {
-                let secret = game_state.random_secret(&mut rng);
-                let diff = game_state.estimate_difficulty(secret);
-                (secret, diff)
-            })
-            .for_each(|(secret, diff)| {
-                println!(
-                    "{} {}",
-                    diff,
-                    secret.iter().map(|w| format!("{}", w)).join(" ")
-                );
-            });
-    } else if opt.quordle {
-        run_quordle(MultiGameState::new(game_state), &opt)?;
-    } else {
-        run_single(game_state, &opt)?;
+    if !game_state.is_valid() {
+        return Err(Error::NoWordsRemaining);
     }
 
-    Ok(())
+    match opt.num_boards() {
+        1 => {
+            let max_guesses = opt.max_guesses.unwrap_or(6);
+            run_single(game_state.with_max_guesses(max_guesses), &opt)
+        }
+        2 => run_multi::<N, 2>(game_state, &opt),
+        4 => run_multi::<N, 4>(game_state, &opt),
+        8 => run_multi::<N, 8>(game_state, &opt),
+        16 => run_multi::<N, 16>(game_state, &opt),
+        32 => run_multi::<N, 32>(game_state, &opt),
+        n => Err(Error::UnsupportedBoardCount(n)),
+    }
+}
+
+fn main() -> Result<(), Error> {
+    let opt = Options::from_args();
+
+    // The adversarial host only plays against a single board.
+    if opt.absurdle && opt.num_boards() != 1 {
+        return Err(Error::AdversaryWithMultipleBoards(opt.num_boards()));
+    }
+
+    match opt.word_length {
+        3 => run::<3>(opt),
+        4 => run::<4>(opt),
+        5 => run::<5>(opt),
+        6 => run::<6>(opt),
+        7 => run::<7>(opt),
+        8 => run::<8>(opt),
+        9 => run::<9>(opt),
+        10 => run::<10>(opt),
+        11 => run::<11>(opt),
+        n => Err(Error::UnsupportedWordLength(n)),
+    }
 }
@@ -1,4 +1,4 @@
-use wordle::Word;
+use wordle::DecisionTree;
 
 use itertools::Itertools;
 
@@ -16,10 +16,19 @@ impl WordlePlotter {
     pub fn add_results<const N: usize>(
         &mut self,
         name: &str,
-        paths: &Vec<Vec<Word<N>>>,
+        tree: &DecisionTree<N>,
+    ) {
+        self.add_guess_counts(name, tree.paths().iter().map(|p| p.len()));
+    }
+
+    // Adds a histogram of the number of guesses needed for each game.
+    pub fn add_guess_counts(
+        &mut self,
+        name: &str,
+        guess_counts: impl Iterator<Item = usize>,
     ) {
         let (bar_x, bar_y): (Vec<usize>, Vec<usize>) =
-            paths.iter().map(|p| p.len()).counts().into_iter().unzip();
+            guess_counts.counts().into_iter().unzip();
         let trace = Bar::new(bar_x, bar_y)
             .name(name)
             .opacity(0.5)
@@ -0,0 +1,86 @@
+use super::{Clue, Word};
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+
+// Upper limit on the number of (guess, secret) pairs stored in a
+// ClueTable.  At two bytes per entry, this keeps the table within a
+// few hundred megabytes.
+const MAX_TABLE_ENTRIES: usize = 1 << 27;
+
+// Precomputed clue ids (as returned by `Clue::id`) for every
+// combination of guess and secret word, so that strategies need not
+// repeat `Word::compare_with_guess` for each turn.
+pub struct ClueTable<const N: usize> {
+    guess_index: HashMap<Word<N>, usize>,
+    secrets: Vec<Word<N>>,
+    ids: Vec<u16>,
+}
+
+impl<const N: usize> ClueTable<N> {
+    // Computes the clue for each guess/secret pair.  Returns None if
+    // the table would be too large to store, or if the clue ids for
+    // this word length do not fit in the table's storage.
+    pub fn new(guesses: &[Word<N>], secrets: &[Word<N>]) -> Option<Self> {
+        let mut guess_index = HashMap::new();
+        let unique_guesses: Vec<Word<N>> = guesses
+            .iter()
+            .filter(|guess| {
+                let next_index = guess_index.len();
+                *guess_index.entry(**guess).or_insert(next_index) == next_index
+            })
+            .copied()
+            .collect();
+
+        let fits_in_storage = Clue::<N>::num_clues() <= (u16::MAX as usize) + 1;
+        let num_entries = unique_guesses.len().saturating_mul(secrets.len());
+        if !fits_in_storage || num_entries > MAX_TABLE_ENTRIES {
+            return None;
+        }
+
+        let ids = unique_guesses
+            .iter()
+            .flat_map(|guess| {
+                secrets
+                    .iter()
+                    .map(move |secret| secret.compare_with_guess(*guess).id())
+            })
+            .map(|id| id as u16)
+            .collect();
+
+        Some(Self {
+            guess_index,
+            secrets: secrets.to_vec(),
+            ids,
+        })
+    }
+
+    // The secret words of the table, in order of their secret id.
+    pub fn secrets(&self) -> &[Word<N>] {
+        &self.secrets
+    }
+
+    // Returns the clue ids for a guess, indexed by secret id.  Returns
+    // None if the guess was not included when building the table.
+    pub fn row(&self, guess: &Word<N>) -> Option<&[u16]> {
+        let num_secrets = self.secrets.len();
+        self.guess_index.get(guess).map(|&guess_id| {
+            &self.ids[guess_id * num_secrets..(guess_id + 1) * num_secrets]
+        })
+    }
+
+    // Returns the clue id for a guess and a secret id, if the guess
+    // was included when building the table.
+    pub fn clue_id(&self, guess: &Word<N>, secret_id: usize) -> Option<usize> {
+        self.row(guess).map(|row| row[secret_id] as usize)
+    }
+}
+
+impl<const N: usize> Debug for ClueTable<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        f.debug_struct("ClueTable")
+            .field("num_guesses", &self.guess_index.len())
+            .field("num_secrets", &self.secrets.len())
+            .finish()
+    }
+}
@@ -0,0 +1,510 @@
+use super::strategy::search::{self, StateKey};
+use super::{Clue, Error, GameState, Strategy, Word};
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use itertools::Itertools;
+use rayon::prelude::*;
+
+// The complete set of guesses made by a deterministic strategy.  Each
+// node holds the guess made, and one subtree for each clue that may
+// be observed in response, other than the all-correct clue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecisionTree<const N: usize> {
+    pub guess: Word<N>,
+
+    // Whether the guess is one of the possible secrets, and may
+    // therefore end the game.
+    pub is_possible_secret: bool,
+
+    // Subtrees for each clue that does not end the game, in order of
+    // `Clue::id`.
+    pub children: Vec<(Clue<N>, DecisionTree<N>)>,
+}
+
+// Subtrees that have already been built, keyed by the state they
+// start from.  The number of guesses already made is part of the key,
+// as a strategy may guess differently with fewer guesses remaining.
+pub struct TranspositionTable<const N: usize> {
+    subtrees: Mutex<HashMap<(StateKey<N>, usize), DecisionTree<N>>>,
+}
+
+impl<const N: usize> TranspositionTable<N> {
+    pub fn new() -> Self {
+        Self {
+            subtrees: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.subtrees.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<const N: usize> Default for TranspositionTable<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> DecisionTree<N> {
+    // Build the tree by applying a strategy for every clue that may be
+    // observed, starting from the state provided.
+    pub fn from_strategy<S: Strategy<N> + ?Sized>(
+        strategy: &S,
+        state: &GameState<N>,
+    ) -> Result<Self, Error> {
+        Self::from_strategy_cached(strategy, state, &TranspositionTable::new())
+    }
+
+    // Build the tree as in `from_strategy`, reusing any subtrees
+    // already present in the transposition table.  The strategy must
+    // be deterministic, and the table should only be shared between
+    // calls that use the same strategy.  Subtrees for each clue are
+    // built in parallel.
+    pub fn from_strategy_cached<S: Strategy<N> + ?Sized>(
+        strategy: &S,
+        state: &GameState<N>,
+        table: &TranspositionTable<N>,
+    ) -> Result<Self, Error> {
+        let key = (search::state_key(state), state.num_guesses);
+        if let Some(tree) = table.subtrees.lock().unwrap().get(&key) {
+            return Ok(tree.clone());
+        }
+
+        let guess = strategy.make_guess(state)?;
+        let partitions = state.partition(&guess);
+
+        let is_possible_secret =
+            partitions.iter().any(|(clue, _child)| clue.all_correct());
+        if !is_possible_secret && partitions.len() == 1 {
+            return Err(Error::StrategyMadeNoProgress(guess.to_string()));
+        }
+
+        let children = partitions
+            .into_iter()
+            .filter(|(clue, _child)| !clue.all_correct())
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|(clue, child)| {
+                let subtree =
+                    Self::from_strategy_cached(strategy, &child, table)?;
+                Ok((clue, subtree))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let tree = Self {
+            guess,
+            is_possible_secret,
+            children,
+        };
+        table.subtrees.lock().unwrap().insert(key, tree.clone());
+        Ok(tree)
+    }
+
+    pub(crate) fn new_leafless(guess: Word<N>) -> Self {
+        Self {
+            guess,
+            is_possible_secret: false,
+            children: Vec::new(),
+        }
+    }
+
+    // Add a path of guesses and clues, ending with an all-correct
+    // clue, to the tree.  Returns an error if the path disagrees with
+    // a guess already in the tree.
+    pub(crate) fn insert_path(
+        &mut self,
+        path: &[(Word<N>, Clue<N>)],
+    ) -> Result<(), Error> {
+        let (guess, clue) = path[0];
+        if guess != self.guess {
+            return Err(Error::InvalidDecisionTree(format!(
+                "Conflicting guesses {} and {} for the same state",
+                self.guess, guess
+            )));
+        }
+
+        if clue.all_correct() {
+            if path.len() > 1 {
+                return Err(Error::InvalidDecisionTree(format!(
+                    "Guesses continue after solving {}",
+                    guess
+                )));
+            }
+            self.is_possible_secret = true;
+            return Ok(());
+        }
+
+        let rest = &path[1..];
+        if rest.is_empty() {
+            return Err(Error::InvalidDecisionTree(format!(
+                "Path ends without solving after guessing {}",
+                guess
+            )));
+        }
+
+        let index = match self
+            .children
+            .iter()
+            .position(|(child_clue, _child)| *child_clue == clue)
+        {
+            Some(index) => index,
+            None => {
+                self.children.push((clue, Self::new_leafless(rest[0].0)));
+                self.children
+                    .sort_by_key(|(child_clue, _child)| child_clue.id());
+                self.children
+                    .iter()
+                    .position(|(child_clue, _child)| *child_clue == clue)
+                    .unwrap()
+            }
+        };
+        self.children[index].1.insert_path(rest)
+    }
+
+    // Checks that the tree can be used to solve a game.  Every clue in
+    // the tree must agree with `Word::compare_with_guess`, every guess
+    // must be allowed (including hard mode restrictions), and every
+    // possible secret must be solved.
+    pub fn verify(&self, state: &GameState<N>) -> Result<(), Error> {
+        let allowed = state
+            .allowed_guesses()
+            .iter()
+            .copied()
+            .chain(state.possible_secrets().iter().copied())
+            .collect::<HashSet<_>>();
+
+        self.secret_paths().iter().try_for_each(|(secret, path)| {
+            path.iter().enumerate().try_for_each(|(i, (guess, clue))| {
+                let uses_hints = path[..i].iter().all(|(prev, prev_clue)| {
+                    guess.uses_hints(*prev, *prev_clue)
+                });
+                if !allowed.contains(guess) {
+                    Err(Error::InvalidDecisionTree(format!(
+                        "Guess {} is not an allowed guess",
+                        guess
+                    )))
+                } else if state.hard_mode && !uses_hints {
+                    Err(Error::InvalidDecisionTree(format!(
+                        "Guess {} does not use the hints from earlier guesses",
+                        guess
+                    )))
+                } else if secret.compare_with_guess(*guess) != *clue {
+                    Err(Error::InvalidDecisionTree(format!(
+                        "Clue {} for guess {} is incorrect for secret {}",
+                        clue, guess, secret
+                    )))
+                } else {
+                    Ok(())
+                }
+            })
+        })?;
+
+        state
+            .possible_secrets()
+            .iter()
+            .copied()
+            .try_for_each(|secret| {
+                self.path(&secret).map(|_path| ()).ok_or_else(|| {
+                    Error::InvalidDecisionTree(format!(
+                        "Secret {} is not solved",
+                        secret
+                    ))
+                })
+            })
+    }
+
+    // Returns the subtree that follows a clue, if any.
+    pub fn child(&self, clue: &Clue<N>) -> Option<&Self> {
+        self.children
+            .iter()
+            .find(|(child_clue, _child)| child_clue == clue)
+            .map(|(_clue, child)| child)
+    }
+
+    // The maximum number of guesses needed to solve any secret.
+    pub fn depth(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(|(_clue, child)| child.depth())
+            .max()
+            .unwrap_or(0)
+    }
+
+    // The number of secrets solved by the tree.
+    pub fn num_leaves(&self) -> usize {
+        (self.is_possible_secret as usize)
+            + self
+                .children
+                .iter()
+                .map(|(_clue, child)| child.num_leaves())
+                .sum::<usize>()
+    }
+
+    // The number of guesses needed to solve each secret, summed over
+    // all secrets.
+    pub fn total_guesses(&self) -> usize {
+        self.num_leaves()
+            + self
+                .children
+                .iter()
+                .map(|(_clue, child)| child.total_guesses())
+                .sum::<usize>()
+    }
+
+    // The mean number of guesses needed to solve a secret.
+    pub fn mean_guesses(&self) -> f64 {
+        (self.total_guesses() as f64) / (self.num_leaves() as f64)
+    }
+
+    // The secrets that need more than `max_guesses` guesses to solve,
+    // and would be lost if the game has a guess limit.
+    pub fn failures(&self, max_guesses: usize) -> Vec<Word<N>> {
+        self.secret_paths()
+            .into_iter()
+            .filter(|(_secret, path)| path.len() > max_guesses)
+            .map(|(secret, _path)| secret)
+            .collect()
+    }
+
+    // The fraction of secrets solved within `max_guesses` guesses.
+    pub fn win_rate(&self, max_guesses: usize) -> f64 {
+        let num_failures = self.failures(max_guesses).len();
+        1.0 - (num_failures as f64) / (self.num_leaves() as f64)
+    }
+
+    // The mean number of guesses needed to solve a secret, weighting
+    // each secret by its weight in the game state.
+    pub fn weighted_mean_guesses(&self, state: &GameState<N>) -> f64 {
+        let (total_guesses, total_weight) = state
+            .possible_secrets()
+            .iter()
+            .copied()
+            .zip(state.secret_weights())
+            .filter_map(|(secret, weight)| {
+                self.path(&secret).map(|path| (path.len(), weight))
+            })
+            .fold((0.0, 0.0), |(guesses, weights), (len, weight)| {
+                (guesses + (len as f64) * weight, weights + weight)
+            });
+        if total_weight > 0.0 {
+            total_guesses / total_weight
+        } else {
+            self.mean_guesses()
+        }
+    }
+
+    // Returns the guesses and clues that would be made for a secret,
+    // ending with the secret itself.  Returns None if the secret is
+    // not solved by this tree.
+    pub fn path(&self, secret: &Word<N>) -> Option<Vec<(Word<N>, Clue<N>)>> {
+        let mut path = Vec::new();
+        let mut node = self;
+        loop {
+            let clue = secret.compare_with_guess(node.guess);
+            path.push((node.guess, clue));
+            if clue.all_correct() {
+                return node.is_possible_secret.then_some(path);
+            }
+            node = node.child(&clue)?;
+        }
+    }
+
+    // Returns every secret solved by the tree, along with the guesses
+    // and clues leading to it.
+    pub fn secret_paths(&self) -> Vec<(Word<N>, Vec<(Word<N>, Clue<N>)>)> {
+        let mut output = Vec::new();
+        self.collect_secret_paths(&mut Vec::new(), &mut output);
+        output
+    }
+
+    fn collect_secret_paths(
+        &self,
+        path: &mut Vec<(Word<N>, Clue<N>)>,
+        output: &mut Vec<(Word<N>, Vec<(Word<N>, Clue<N>)>)>,
+    ) {
+        if self.is_possible_secret {
+            let mut solved = path.clone();
+            solved
+                .push((self.guess, self.guess.compare_with_guess(self.guess)));
+            output.push((self.guess, solved));
+        }
+        self.children.iter().for_each(|(clue, child)| {
+            path.push((self.guess, *clue));
+            child.collect_secret_paths(path, output);
+            path.pop();
+        });
+    }
+
+    // Returns the sequence of guesses made for each secret, in order
+    // of `Clue::id` at each guess.
+    pub fn paths(&self) -> Vec<Vec<Word<N>>> {
+        self.secret_paths()
+            .into_iter()
+            .map(|(_secret, path)| {
+                path.into_iter().map(|(guess, _clue)| guess).collect()
+            })
+            .collect()
+    }
+
+    // Returns the sequence of guesses made for each secret of `state`,
+    // in the order produced by a depth-first walk of the game.  Each
+    // guess is followed by the clues it may receive in reverse order
+    // of the first secret giving them.
+    pub fn paths_from(&self, state: &GameState<N>) -> Vec<Vec<Word<N>>> {
+        let mut final_paths = Vec::new();
+        let mut stack = vec![(Vec::new(), self, state.clone())];
+
+        while let Some((mut path, node, state)) = stack.pop() {
+            path.push(node.guess);
+
+            state
+                .possible_secrets()
+                .iter()
+                .map(|secret| secret.compare_with_guess(node.guess))
+                .unique()
+                .for_each(|clue| {
+                    if clue.all_correct() {
+                        final_paths.push(path.clone());
+                    } else if let Some(child) = node.child(&clue) {
+                        stack.push((
+                            path.clone(),
+                            child,
+                            state.after_guess(node.guess, clue),
+                        ))
+                    }
+                })
+        }
+
+        final_paths
+    }
+}
+
+// Replays the tree.  The current node is found by following each
+// guess whose clue is already known, as all remaining secrets give
+// the same clue for it.
+impl<const N: usize> Strategy<N> for DecisionTree<N> {
+    fn make_guess(&self, state: &GameState<N>) -> Result<Word<N>, Error> {
+        let mut node = self;
+        loop {
+            let clues = state
+                .possible_secrets()
+                .iter()
+                .copied()
+                .map(|secret| secret.compare_with_guess(node.guess))
+                .unique()
+                .collect::<Vec<_>>();
+            match clues.as_slice() {
+                [] => return Err(Error::NoWordsRemaining),
+                [clue] if !clue.all_correct() => {
+                    node = node.child(clue).ok_or_else(|| {
+                        Error::InvalidDecisionTree(format!(
+                            "No guess after {} with clue {}",
+                            node.guess, clue
+                        ))
+                    })?;
+                }
+                _ => return Ok(node.guess),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::MiniMax;
+
+    #[test]
+    fn test_tree_queries() -> Result<(), Error> {
+        let wordle = GameState::<5>::from_wordle();
+        let words = wordle.possible_secrets()[..200].to_vec();
+        let state = GameState::new(words.clone(), words.clone());
+
+        let tree = DecisionTree::from_strategy(&MiniMax, &state)?;
+        assert_eq!(tree.num_leaves(), words.len());
+
+        let paths = tree.paths();
+        assert_eq!(paths.len(), words.len());
+        assert_eq!(
+            tree.total_guesses(),
+            paths.iter().map(|path| path.len()).sum::<usize>()
+        );
+        assert_eq!(
+            tree.depth(),
+            paths.iter().map(|path| path.len()).max().unwrap()
+        );
+        assert!(tree.failures(tree.depth()).is_empty());
+        assert_eq!(tree.win_rate(tree.depth()), 1.0);
+        assert!(!tree.failures(tree.depth() - 1).is_empty());
+
+        for secret in words.iter() {
+            let path = tree.path(secret).unwrap();
+            assert_eq!(path.last().unwrap().0, *secret);
+            assert!(path.iter().all(|(guess, clue)| secret
+                .compare_with_guess(*guess)
+                == *clue));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_transposition_table() -> Result<(), Error> {
+        let wordle = GameState::<5>::from_wordle();
+        let words = wordle.possible_secrets()[..200].to_vec();
+        let state = GameState::new(words.clone(), words);
+
+        let table = TranspositionTable::new();
+        let tree =
+            DecisionTree::from_strategy_cached(&MiniMax, &state, &table)?;
+        assert!(!table.is_empty());
+        assert_eq!(tree, DecisionTree::from_strategy(&MiniMax, &state)?);
+
+        let subtree = &tree.children[0].1;
+        let child = state.after_guess(tree.guess, tree.children[0].0);
+        assert_eq!(
+            DecisionTree::from_strategy_cached(&MiniMax, &child, &table)?,
+            *subtree
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_listing_roundtrip() -> Result<(), Error> {
+        let wordle = GameState::<5>::from_wordle();
+        let words = wordle.possible_secrets()[..200].to_vec();
+        let state = GameState::new(words.clone(), words);
+
+        let tree = DecisionTree::from_strategy(&MiniMax, &state)?;
+        let listing = tree.to_string();
+        assert_eq!(listing.lines().count(), tree.num_leaves());
+
+        let loaded: DecisionTree<5> = listing.parse()?;
+        assert_eq!(loaded, tree);
+        loaded.verify(&state)?;
+        assert_eq!(loaded.decision_tree(&state)?, tree);
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_rejects_bad_clue() -> Result<(), Error> {
+        let state =
+            GameState::<5>::from_iter(["apple", "table"].iter().copied());
+
+        let tree: DecisionTree<5> =
+            "table BYBGG apple GGGGG\ntable GGGGG\n".parse()?;
+        tree.verify(&state)?;
+
+        let tree: DecisionTree<5> =
+            "table BBYGG apple GGGGG\ntable GGGGG\n".parse()?;
+        assert!(tree.verify(&state).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use super::{Clue, GameState, Word};
+
+use itertools::Itertools;
+
+// A change to one turn of a game's history, which may correct a typo
+// made when entering the guess or the clue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Correction<const N: usize> {
+    // The tiles of the clue received on this turn.  Costs one edit
+    // per tile changed.
+    Clue { turn: usize, clue: Clue<N> },
+
+    // The word guessed on this turn, which differs from the word
+    // entered by at most two letters.  Costs one edit.
+    Guess { turn: usize, guess: Word<N> },
+}
+
+// Maximum number of letters by which a corrected guess may differ
+// from the word entered.
+const MAX_GUESS_TYPO: usize = 2;
+
+impl<const N: usize> GameState<N> {
+    // Given the guesses and clues entered since this state, finds the
+    // smallest sets of corrections that would leave at least one
+    // possible secret.  Returns an empty set if the history is already
+    // consistent, and no sets if nothing can be corrected.
+    pub fn diagnose(
+        &self,
+        history: &[(Word<N>, Clue<N>)],
+    ) -> Vec<Vec<Correction<N>>> {
+        // Words that could have been guessed instead, for each turn.
+        let similar_guesses = history
+            .iter()
+            .map(|(guess, _clue)| {
+                self.allowed_guesses()
+                    .iter()
+                    .filter(|word| *word != guess)
+                    .filter(|word| {
+                        Self::num_differences(word.letters, guess.letters)
+                            <= MAX_GUESS_TYPO
+                    })
+                    .copied()
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let candidates = self
+            .possible_secrets()
+            .iter()
+            .copied()
+            .map(|secret| {
+                let corrections = history
+                    .iter()
+                    .zip(similar_guesses.iter())
+                    .enumerate()
+                    .map(|(turn, ((guess, clue), similar))| {
+                        Self::correct_turn(secret, turn, *guess, *clue, similar)
+                    })
+                    .collect::<Vec<_>>();
+                let cost = corrections
+                    .iter()
+                    .map(|(cost, _correction)| *cost)
+                    .sum::<usize>();
+                let corrections = corrections
+                    .into_iter()
+                    .filter_map(|(_cost, correction)| correction)
+                    .collect::<Vec<_>>();
+                (cost, corrections)
+            })
+            .collect::<Vec<_>>();
+
+        let min_cost = candidates.iter().map(|(cost, _)| *cost).min();
+        candidates
+            .into_iter()
+            .filter(|(cost, _corrections)| Some(*cost) == min_cost)
+            .map(|(_cost, corrections)| corrections)
+            .unique()
+            .collect()
+    }
+
+    // Returns the cheapest correction to a turn that would make it
+    // consistent with `secret`, along with its cost.
+    fn correct_turn(
+        secret: Word<N>,
+        turn: usize,
+        guess: Word<N>,
+        clue: Clue<N>,
+        similar_guesses: &[Word<N>],
+    ) -> (usize, Option<Correction<N>>) {
+        let expected = secret.compare_with_guess(guess);
+        let num_tiles = Self::num_differences(expected.tiles, clue.tiles);
+        if num_tiles <= 1 {
+            let correction = (num_tiles == 1).then_some(Correction::Clue {
+                turn,
+                clue: expected,
+            });
+            return (num_tiles, correction);
+        }
+
+        similar_guesses
+            .iter()
+            .find(|word| secret.compare_with_guess(**word) == clue)
+            .map(|word| (1, Some(Correction::Guess { turn, guess: *word })))
+            .unwrap_or((
+                num_tiles,
+                Some(Correction::Clue {
+                    turn,
+                    clue: expected,
+                }),
+            ))
+    }
+
+    fn num_differences<T: PartialEq>(a: [T; N], b: [T; N]) -> usize {
+        a.iter().zip(b.iter()).filter(|(a, b)| a != b).count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn test_diagnose_tile_typo() -> Result<(), Error> {
+        let state = GameState::<5>::from_iter(
+            ["apple", "table", "farts", "ghost"].iter().copied(),
+        );
+        let guess = "chart".parse()?;
+        let history = vec![(guess, "_G_GG".parse()?)];
+        assert!(!state.after_guess(guess, history[0].1).is_valid());
+
+        let diagnosis = state.diagnose(&history);
+        assert_eq!(
+            diagnosis,
+            vec![vec![Correction::Clue {
+                turn: 0,
+                clue: "_G__G".parse()?,
+            }]]
+        );
+
+        let consistent = vec![(guess, "_G__G".parse()?)];
+        assert_eq!(state.diagnose(&consistent), vec![Vec::new()]);
+        Ok(())
+    }
+}
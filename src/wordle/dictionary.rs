@@ -0,0 +1,99 @@
+use super::{ClueTable, Word};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// Word lists and precomputed data that do not change over the course
+// of a game.  A dictionary is shared by every `GameState` derived from
+// the same initial state, which refer to secrets by their index
+// (secret id) within the dictionary.
+#[derive(Debug, Clone)]
+pub struct Dictionary<const N: usize> {
+    secrets: Vec<Word<N>>,
+    secret_index: HashMap<Word<N>, usize>,
+
+    // Relative likelihood of each secret, indexed by secret id.  If
+    // None, all secrets are equally likely.
+    secret_weights: Option<Vec<f64>>,
+
+    // Precomputed clues, indexed by secret id.
+    clue_table: Option<Arc<ClueTable<N>>>,
+}
+
+impl<const N: usize> Dictionary<N> {
+    pub fn new(secrets: Vec<Word<N>>) -> Self {
+        let mut secret_index = HashMap::new();
+        secrets.iter().enumerate().rev().for_each(|(i, secret)| {
+            secret_index.insert(*secret, i);
+        });
+        Self {
+            secrets,
+            secret_index,
+            secret_weights: None,
+            clue_table: None,
+        }
+    }
+
+    // Assign a weight to each secret.  Secrets without a positive,
+    // finite weight are given the smallest such weight provided, so
+    // that they remain possible but unlikely.  If no weight provided
+    // is usable, every secret has a weight of 1.
+    pub fn with_weights(self, weights: &HashMap<Word<N>, f64>) -> Self {
+        let is_usable = |weight: &f64| weight.is_finite() && *weight > 0.0;
+        let default_weight = weights
+            .values()
+            .copied()
+            .filter(is_usable)
+            .min_by(|a, b| a.total_cmp(b))
+            .unwrap_or(1.0);
+        let secret_weights = self
+            .secrets
+            .iter()
+            .map(|secret| {
+                weights
+                    .get(secret)
+                    .copied()
+                    .filter(is_usable)
+                    .unwrap_or(default_weight)
+            })
+            .collect();
+        Self {
+            secret_weights: Some(secret_weights),
+            ..self
+        }
+    }
+
+    // Precompute the clues for each guess against every secret.  If
+    // the word lists are too large for a table, the dictionary is
+    // returned unchanged.
+    pub fn with_clue_table(self, guesses: &[Word<N>]) -> Self {
+        let clue_table = ClueTable::new(guesses, &self.secrets).map(Arc::new);
+        Self { clue_table, ..self }
+    }
+
+    pub fn secrets(&self) -> &[Word<N>] {
+        &self.secrets
+    }
+
+    pub fn secret(&self, secret_id: usize) -> Word<N> {
+        self.secrets[secret_id]
+    }
+
+    pub fn secret_id(&self, word: &Word<N>) -> Option<usize> {
+        self.secret_index.get(word).copied()
+    }
+
+    pub fn is_weighted(&self) -> bool {
+        self.secret_weights.is_some()
+    }
+
+    pub fn secret_weight(&self, secret_id: usize) -> f64 {
+        self.secret_weights
+            .as_ref()
+            .map_or(1.0, |weights| weights[secret_id])
+    }
+
+    pub fn clue_table(&self) -> Option<&ClueTable<N>> {
+        self.clue_table.as_deref()
+    }
+}
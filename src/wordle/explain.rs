@@ -0,0 +1,121 @@
+use super::{Clue, GameState, Word};
+
+// The possible secrets that would produce one clue for a guess.
+#[derive(Debug, Clone)]
+pub struct ClueBucket<const N: usize> {
+    pub clue: Clue<N>,
+    pub num_secrets: usize,
+    pub weight: f64,
+
+    // Up to `MAX_EXAMPLES` of the secrets in this bucket.
+    pub examples: Vec<Word<N>>,
+}
+
+// How a guess splits the possible secrets of a game state.  Buckets
+// are ordered from largest to smallest.  If the secrets are weighted,
+// the expected size and entropy use the weights.
+#[derive(Debug, Clone)]
+pub struct PartitionReport<const N: usize> {
+    pub guess: Word<N>,
+    pub buckets: Vec<ClueBucket<N>>,
+
+    // The number of secrets in the largest bucket.
+    pub worst_case: usize,
+
+    // The expected number of secrets remaining after the guess.
+    pub expected: f64,
+
+    // The Shannon entropy of the clue distribution, in bits.
+    pub entropy: f64,
+}
+
+const MAX_EXAMPLES: usize = 5;
+
+impl<const N: usize> GameState<N> {
+    // Returns how `guess` would split the possible secrets.
+    pub fn explain(&self, guess: &Word<N>) -> PartitionReport<N> {
+        let mut buckets = (0..Clue::<N>::num_clues())
+            .map(|id| ClueBucket {
+                clue: Clue::from_id(id),
+                num_secrets: 0,
+                weight: 0.0,
+                examples: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        self.clue_ids(guess)
+            .zip(
+                self.possible_secrets()
+                    .iter()
+                    .copied()
+                    .zip(self.secret_weights()),
+            )
+            .for_each(|(id, (secret, weight))| {
+                let bucket = &mut buckets[id];
+                bucket.num_secrets += 1;
+                bucket.weight += weight;
+                if bucket.examples.len() < MAX_EXAMPLES {
+                    bucket.examples.push(secret);
+                }
+            });
+        buckets.retain(|bucket| bucket.num_secrets > 0);
+        buckets.sort_by_key(|bucket| std::cmp::Reverse(bucket.num_secrets));
+
+        let total = self.total_weight();
+        let probabilities = buckets
+            .iter()
+            .map(|bucket| bucket.weight / total)
+            .collect::<Vec<_>>();
+
+        PartitionReport {
+            guess: *guess,
+            worst_case: buckets.first().map_or(0, |bucket| bucket.num_secrets),
+            expected: buckets
+                .iter()
+                .zip(probabilities.iter())
+                .map(|(bucket, p)| p * bucket.num_secrets as f64)
+                .sum(),
+            entropy: probabilities
+                .iter()
+                .filter(|&&p| p > 0.0)
+                .map(|p| -p * p.log2())
+                .sum(),
+            buckets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn test_explain_partition() -> Result<(), Error> {
+        let state = GameState::<5>::from_iter(
+            ["apple", "table", "farts", "ghost", "cable"]
+                .iter()
+                .copied(),
+        );
+        let report = state.explain(&"table".parse()?);
+
+        let sizes = report
+            .buckets
+            .iter()
+            .map(|bucket| bucket.num_secrets)
+            .collect::<Vec<_>>();
+        assert_eq!(sizes.iter().sum::<usize>(), 5);
+        assert_eq!(report.worst_case, sizes[0]);
+        assert!(report.buckets.iter().all(|bucket| bucket
+            .examples
+            .iter()
+            .all(
+                |secret| secret.compare_with_guess(report.guess) == bucket.clue
+            )));
+
+        let expected =
+            sizes.iter().map(|&size| (size * size) as f64).sum::<f64>() / 5.0;
+        assert!((report.expected - expected).abs() < 1e-9);
+        assert!(report.entropy > 0.0 && report.entropy <= 5.0f64.log2());
+        Ok(())
+    }
+}
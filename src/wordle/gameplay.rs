@@ -1,7 +1,10 @@
-use super::{MultiStrategy, Strategy};
+use super::{Dictionary, MultiStrategy, Strategy};
 
+use std::collections::HashMap;
 use std::convert::TryInto;
+use std::sync::Arc;
 
+use itertools::{Either, Itertools};
 use rand::Rng;
 
 #[derive(Debug)]
@@ -13,6 +16,18 @@ pub enum Error {
     NoWordsRemaining,
     NotTileChar(char),
     IncorrectNumberOfWords,
+    NoSolutionWithinDepth,
+    StrategyMadeNoProgress(String),
+    InvalidDecisionTree(String),
+    TreeFileError(std::io::Error),
+    InvalidWeightFile(String),
+    UnsupportedWordLength(usize),
+    UnsupportedBoardCount(usize),
+    AdversaryWithMultipleBoards(usize),
+    InvalidShareText(String),
+    ShareFileError(std::io::Error),
+    InvalidHistory(String),
+    HistoryFileError(std::io::Error),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
@@ -35,13 +50,38 @@ pub struct Clue<const N: usize> {
 #[derive(Debug, Clone)]
 pub struct GameState<const N: usize> {
     pub made_correct_guess: bool,
-    pub allowed_guesses: Vec<Word<N>>,
-    pub possible_secrets: Vec<Word<N>>,
+
+    // The number of guesses made so far, and the number after which
+    // the game is lost if the secret has not been found.  If
+    // `max_guesses` is None, there is no limit.
+    pub num_guesses: usize,
+    pub max_guesses: Option<usize>,
+
+    // If true, every revealed hint must be used in later guesses, and
+    // `allowed_guesses` is reduced after each guess.
+    pub hard_mode: bool,
+
+    // Word lists shared between all states of a game.  Cloning a
+    // state does not copy the dictionary.
+    dictionary: Arc<Dictionary<N>>,
+    allowed_guesses: Arc<Vec<Word<N>>>,
+
+    // The secrets consistent with all clues received so far, along
+    // with the id of each within the dictionary, in increasing order.
+    possible_secrets: Vec<Word<N>>,
+    secret_ids: Vec<u32>,
 }
 
 #[derive(Debug, Clone)]
 pub struct MultiGameState<const N: usize, const GAMES: usize> {
     pub games: [GameState<N>; GAMES],
+
+    // The guesses allowed before any hints were revealed, and each
+    // guess made so far along with its clues.  In hard mode, these let
+    // the allowed guesses be rebuilt once a puzzle is solved, since a
+    // solved puzzle's hints no longer apply.
+    initial_guesses: Arc<Vec<Word<N>>>,
+    history: Vec<(Word<N>, [Clue<N>; GAMES])>,
 }
 
 impl<const N: usize> Word<N> {
@@ -68,6 +108,30 @@ impl<const N: usize> Word<N> {
 
         Clue { tiles }
     }
+
+    // Returns whether this word would be allowed as a guess in hard
+    // mode, after `guess` received `clue`.  Correct letters must be
+    // repeated in the same position, and letters in the wrong
+    // position must be included somewhere in the word.
+    pub fn uses_hints(&self, guess: Word<N>, clue: Clue<N>) -> bool {
+        let mut required_letters = [0_u8; 26];
+        let mut available_letters = [0_u8; 26];
+        for i in 0..N {
+            match clue[i] {
+                Tile::Correct if self[i] != guess[i] => return false,
+                Tile::Correct | Tile::WrongPosition => {
+                    required_letters[guess[i] as usize] += 1;
+                }
+                Tile::NotPresentInWord => {}
+            }
+            available_letters[self[i] as usize] += 1;
+        }
+
+        required_letters
+            .iter()
+            .zip(available_letters.iter())
+            .all(|(required, available)| required <= available)
+    }
 }
 
 impl<const N: usize> Clue<N> {
@@ -75,6 +139,36 @@ impl<const N: usize> Clue<N> {
         self.iter().all(|&tile| tile == Tile::Correct)
     }
 
+    // Returns whether any secret word could produce this clue for a
+    // guess.  Tiles for a letter in the wrong position must come before
+    // any tiles showing the letter is not present, and each letter in
+    // the wrong position must fit somewhere it was not guessed.
+    pub fn is_possible_for(&self, guess: Word<N>) -> bool {
+        let mut seen_absent = [false; 26];
+        let mut num_wrong_position = [0_usize; 26];
+        for i in 0..N {
+            let letter = guess[i] as usize;
+            match self[i] {
+                Tile::Correct => {}
+                Tile::WrongPosition if seen_absent[letter] => return false,
+                Tile::WrongPosition => num_wrong_position[letter] += 1,
+                Tile::NotPresentInWord => seen_absent[letter] = true,
+            }
+        }
+
+        let open_positions = (0..N)
+            .filter(|&i| self[i] != Tile::Correct)
+            .collect::<Vec<_>>();
+        let fits =
+            num_wrong_position.iter().enumerate().all(|(letter, num)| {
+                *num <= open_positions
+                    .iter()
+                    .filter(|&&i| guess[i] as usize != letter)
+                    .count()
+            });
+        fits && num_wrong_position.iter().sum::<usize>() <= open_positions.len()
+    }
+
     pub fn num_clues() -> usize {
         3_usize.pow(N as u32)
     }
@@ -110,17 +204,170 @@ impl<const N: usize> Clue<N> {
 }
 
 impl<const N: usize> GameState<N> {
+    pub fn new(
+        allowed_guesses: Vec<Word<N>>,
+        possible_secrets: Vec<Word<N>>,
+    ) -> Self {
+        let secret_ids = (0..possible_secrets.len() as u32).collect();
+        Self {
+            made_correct_guess: false,
+            num_guesses: 0,
+            max_guesses: None,
+            hard_mode: false,
+            dictionary: Arc::new(Dictionary::new(possible_secrets.clone())),
+            allowed_guesses: Arc::new(allowed_guesses),
+            possible_secrets,
+            secret_ids,
+        }
+    }
+
+    pub fn with_hard_mode(self, hard_mode: bool) -> Self {
+        Self { hard_mode, ..self }
+    }
+
+    pub fn with_max_guesses(self, max_guesses: usize) -> Self {
+        Self {
+            max_guesses: Some(max_guesses),
+            ..self
+        }
+    }
+
+    // Assign a weight to each possible secret, as described in
+    // `Dictionary::with_weights`.
+    pub fn with_weights(self, weights: &HashMap<Word<N>, f64>) -> Self {
+        let dictionary =
+            Arc::new((*self.dictionary).clone().with_weights(weights));
+        Self { dictionary, ..self }
+    }
+
+    // Precompute the clues for every allowed guess against every
+    // secret in the dictionary, to be used by later calls to
+    // `clue_ids`.  If the word lists are too large for a table, the
+    // state is returned unchanged.
+    pub fn with_clue_table(self) -> Self {
+        let dictionary = Arc::new(
+            (*self.dictionary)
+                .clone()
+                .with_clue_table(&self.allowed_guesses),
+        );
+        Self { dictionary, ..self }
+    }
+
+    // The word lists shared by every state of this game.
+    pub fn dictionary(&self) -> &Dictionary<N> {
+        &self.dictionary
+    }
+
+    // Words that may be guessed next.
+    pub fn allowed_guesses(&self) -> &[Word<N>] {
+        &self.allowed_guesses
+    }
+
+    // Secrets that are consistent with all clues received so far, in
+    // the order they appear in the dictionary.
+    pub fn possible_secrets(&self) -> &[Word<N>] {
+        &self.possible_secrets
+    }
+
+    // Returns whether a word may be guessed next.  In hard mode, the
+    // guess must also use every hint revealed so far.
+    pub fn is_allowed_guess(&self, guess: &Word<N>) -> bool {
+        self.allowed_guesses.contains(guess) || self.is_possible_secret(guess)
+    }
+
+    pub fn num_possible_secrets(&self) -> usize {
+        self.secret_ids.len()
+    }
+
+    pub fn is_possible_secret(&self, word: &Word<N>) -> bool {
+        self.dictionary.secret_id(word).is_some_and(|id| {
+            self.secret_ids.binary_search(&(id as u32)).is_ok()
+        })
+    }
+
+    // Returns whether the possible secrets have been assigned weights.
+    pub fn is_weighted(&self) -> bool {
+        self.dictionary.is_weighted()
+    }
+
+    // Returns the weight of each element of `possible_secrets`.
+    pub fn secret_weights(&self) -> impl Iterator<Item = f64> + '_ {
+        self.secret_ids
+            .iter()
+            .map(move |&id| self.dictionary.secret_weight(id as usize))
+    }
+
+    // Returns the weight of a secret, or None if it is not a possible
+    // secret.
+    pub fn secret_weight(&self, secret: &Word<N>) -> Option<f64> {
+        self.is_possible_secret(secret).then(|| {
+            let id = self.dictionary.secret_id(secret).unwrap();
+            self.dictionary.secret_weight(id)
+        })
+    }
+
+    // Returns the total weight of all possible secrets.
+    pub fn total_weight(&self) -> f64 {
+        self.secret_weights().sum()
+    }
+
+    // Returns the total weight of the possible secrets that would
+    // produce each clue, indexed by `Clue::id`, if `guess` were made.
+    pub fn clue_weights(&self, guess: &Word<N>) -> Vec<f64> {
+        let mut weights = vec![0.0; Clue::<N>::num_clues()];
+        self.clue_ids(guess)
+            .zip(self.secret_weights())
+            .for_each(|(id, weight)| weights[id] += weight);
+        weights
+    }
+
+    // Returns the clue id (as returned by `Clue::id`) for each
+    // element of `possible_secrets`, if `guess` were made.  Uses the
+    // precomputed clue table where possible.
+    pub fn clue_ids<'a>(
+        &'a self,
+        guess: &Word<N>,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let guess = *guess;
+        let table_row = self
+            .dictionary
+            .clue_table()
+            .and_then(|table| table.row(&guess));
+        match table_row {
+            Some(row) => Either::Left(
+                self.secret_ids
+                    .iter()
+                    .map(move |&id| row[id as usize] as usize),
+            ),
+            None => Either::Right(
+                self.possible_secrets()
+                    .iter()
+                    .copied()
+                    .map(move |secret| secret.compare_with_guess(guess).id()),
+            ),
+        }
+    }
+
     // Returns whether the game has finished by having guessed the
     // secret word.
     pub fn is_finished(&self) -> bool {
         self.made_correct_guess
     }
 
+    // Returns whether the game has been lost by reaching the maximum
+    // number of guesses without guessing the secret word.
+    pub fn is_lost(&self) -> bool {
+        !self.made_correct_guess
+            && self
+                .max_guesses
+                .is_some_and(|max_guesses| self.num_guesses >= max_guesses)
+    }
+
     // Returns whether this represents a valid state of the game.  If
     // all possible secrets have been eliminated, something has gone
     // wrong internally.
     pub fn is_valid(&self) -> bool {
-        !self.possible_secrets.is_empty()
+        !self.secret_ids.is_empty()
     }
 
     // Returns the game state that would occur after applying a guess
@@ -130,39 +377,110 @@ impl<const N: usize> GameState<N> {
         guess: Word<N>,
         observed_result: Clue<N>,
     ) -> Self {
-        let secret = self
-            .possible_secrets
+        let observed_id = observed_result.id();
+        let is_consistent = self
+            .clue_ids(&guess)
+            .map(|id| id == observed_id)
+            .collect::<Vec<_>>();
+        self.filtered(&is_consistent, guess, observed_result)
+    }
+
+    // Returns each game state that could occur after applying a
+    // guess, along with the clue that would produce it.  States are
+    // ordered by `Clue::id`.
+    pub fn partition(&self, guess: &Word<N>) -> Vec<(Clue<N>, Self)> {
+        let clue_ids = self.clue_ids(guess).collect::<Vec<_>>();
+        clue_ids
             .iter()
-            .filter(|secret| {
-                secret.compare_with_guess(guess) == observed_result
-            })
             .copied()
-            .collect();
-        let made_correct_guess =
-            self.made_correct_guess || observed_result.all_correct();
+            .sorted()
+            .dedup()
+            .map(|id| {
+                let clue = Clue::from_id(id);
+                let is_consistent = clue_ids
+                    .iter()
+                    .map(|&other| other == id)
+                    .collect::<Vec<_>>();
+                (clue, self.filtered(&is_consistent, *guess, clue))
+            })
+            .collect()
+    }
+
+    // Returns the game state with only the possible secrets for which
+    // `keep` is true, after having received a clue for a guess.
+    fn filtered(&self, keep: &[bool], guess: Word<N>, clue: Clue<N>) -> Self {
+        let (possible_secrets, secret_ids) = self
+            .possible_secrets
+            .iter()
+            .zip(self.secret_ids.iter())
+            .zip(keep.iter())
+            .filter(|(_secret, &keep)| keep)
+            .map(|(secret, _keep)| secret)
+            .unzip();
+
+        let allowed_guesses = if self.hard_mode {
+            Arc::new(
+                self.allowed_guesses
+                    .iter()
+                    .filter(|word| word.uses_hints(guess, clue))
+                    .copied()
+                    .collect(),
+            )
+        } else {
+            self.allowed_guesses.clone()
+        };
+
         Self {
-            made_correct_guess,
-            allowed_guesses: self.allowed_guesses.clone(),
-            possible_secrets: secret,
+            made_correct_guess: self.made_correct_guess || clue.all_correct(),
+            num_guesses: self.num_guesses + 1,
+            max_guesses: self.max_guesses,
+            hard_mode: self.hard_mode,
+            dictionary: self.dictionary.clone(),
+            allowed_guesses,
+            possible_secrets,
+            secret_ids,
         }
     }
 
     // An iterator that returns the results of repeated application of
     // a strategy.  The last element of the iterator will return true
-    // for `GameState.is_finished`.
+    // for either `is_finished` or `is_lost`.
     pub fn simulate_strategy<'a, S: Strategy<N>>(
         &self,
         secret_word: Word<N>,
         strategy: &'a S,
     ) -> impl Iterator<Item = Result<(Option<(Word<N>, Clue<N>)>, Self), Error>> + 'a
+    {
+        self.simulate_with(strategy, move |_state, guess| {
+            Ok(secret_word.compare_with_guess(guess))
+        })
+    }
+
+    // As `simulate_strategy`, but playing against an adversarial host
+    // instead of a fixed secret.  See `adversarial_clue`.
+    pub fn simulate_against_adversary<'a, S: Strategy<N>>(
+        &self,
+        strategy: &'a S,
+    ) -> impl Iterator<Item = Result<(Option<(Word<N>, Clue<N>)>, Self), Error>> + 'a
+    {
+        self.simulate_with(strategy, |state, guess| {
+            state.adversarial_clue(&guess)
+        })
+    }
+
+    fn simulate_with<'a, S: Strategy<N>>(
+        &self,
+        strategy: &'a S,
+        clue_for: impl Fn(&Self, Word<N>) -> Result<Clue<N>, Error> + 'a,
+    ) -> impl Iterator<Item = Result<(Option<(Word<N>, Clue<N>)>, Self), Error>> + 'a
     {
         std::iter::successors(
             Some(Ok((None, self.clone()))),
             move |res_state| {
                 if let Ok((_prev_clue, state)) = res_state {
-                    (!state.is_finished()).then(|| {
+                    (!state.is_finished() && !state.is_lost()).then(|| {
                         let guess = strategy.make_guess(state)?;
-                        let clue = secret_word.compare_with_guess(guess);
+                        let clue = clue_for(state, guess)?;
                         let new_state = state.after_guess(guess, clue);
                         Ok((Some((guess, clue)), new_state))
                     })
@@ -173,9 +491,29 @@ impl<const N: usize> GameState<N> {
         )
     }
 
+    // Returns the clue that an adversarial host, as in Absurdle, would
+    // give for a guess.  Rather than fixing a secret in advance, the
+    // host keeps as many possible secrets as it can, breaking ties in
+    // favor of the clue with the fewest correct tiles, then by clue id.
+    pub fn adversarial_clue(&self, guess: &Word<N>) -> Result<Clue<N>, Error> {
+        self.clue_ids(guess)
+            .counts()
+            .into_iter()
+            .max_by_key(|&(id, count)| {
+                let num_correct = Clue::<N>::from_id(id)
+                    .iter()
+                    .filter(|&&tile| tile == Tile::Correct)
+                    .count();
+                (count, std::cmp::Reverse(num_correct), id)
+            })
+            .map(|(id, _count)| Clue::from_id(id))
+            .ok_or(Error::NoWordsRemaining)
+    }
+
     // Pick a random secret that is compatible with this game state.
     pub fn random_secret<T: Rng>(&self, rng: &mut T) -> Word<N> {
-        self.possible_secrets[rng.gen_range(0..self.possible_secrets.len())]
+        let id = self.secret_ids[rng.gen_range(0..self.secret_ids.len())];
+        self.dictionary.secret(id as usize)
     }
 }
 
@@ -186,7 +524,11 @@ impl<const N: usize, const GAMES: usize> MultiGameState<N, GAMES> {
             .collect::<Vec<_>>()
             .try_into()
             .unwrap();
-        Self { games }
+        Self {
+            games,
+            initial_guesses: single.allowed_guesses,
+            history: Vec::new(),
+        }
     }
 
     // Returns whether the game has finished by having guessed the
@@ -195,6 +537,29 @@ impl<const N: usize, const GAMES: usize> MultiGameState<N, GAMES> {
         self.games.iter().all(|game| game.is_finished())
     }
 
+    // Returns whether the game has been lost by reaching the maximum
+    // number of guesses without solving every puzzle.
+    pub fn is_lost(&self) -> bool {
+        self.games.iter().any(|game| game.is_lost())
+    }
+
+    // Returns whether a word may be guessed next.  In hard mode, the
+    // guess must use the hints revealed on every puzzle.
+    pub fn is_allowed_guess(&self, guess: &Word<N>) -> bool {
+        if self.games.iter().any(|game| game.hard_mode) {
+            self.games[0].allowed_guesses.contains(guess)
+        } else {
+            self.games.iter().any(|game| game.is_allowed_guess(guess))
+        }
+    }
+
+    // Sets the maximum number of guesses for every puzzle.  See
+    // `default_max_guesses` for the limits of common variants.
+    pub fn with_max_guesses(self, max_guesses: usize) -> Self {
+        let games = self.games.map(|game| game.with_max_guesses(max_guesses));
+        Self { games, ..self }
+    }
+
     // Returns whether this represents a valid state of the game.  If
     // any puzzle has eliminated all possible secrets, then something
     // has gone wrong internally.
@@ -205,21 +570,74 @@ impl<const N: usize, const GAMES: usize> MultiGameState<N, GAMES> {
     // Returns the game state that would occur after applying a guess
     // and receiving a clue.
     pub fn after_guess(&self, guess: Word<N>, clues: [Clue<N>; GAMES]) -> Self {
-        let games = self
+        let mut games = self
             .games
             .iter()
             .zip(clues.iter())
             .map(|(game, clue)| game.after_guess(guess, *clue))
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap();
+            .collect::<Vec<_>>();
+
+        let mut history = self.history.clone();
+        history.push((guess, clues));
+
+        // In hard mode, a guess must use the hints revealed on every
+        // unsolved puzzle, not just its own.  When a puzzle is solved,
+        // its earlier hints stop applying, so the allowed guesses are
+        // rebuilt from the start of the game.
+        if self.games.iter().any(|game| game.hard_mode) {
+            let unsolved = games
+                .iter()
+                .map(|game| !game.is_finished())
+                .collect::<Vec<_>>();
+            let newly_solved = self
+                .games
+                .iter()
+                .zip(unsolved.iter())
+                .any(|(game, &unsolved)| !game.is_finished() && !unsolved);
+            let (candidates, hints) = if newly_solved {
+                (&self.initial_guesses, &history[..])
+            } else {
+                (
+                    &self.games[0].allowed_guesses,
+                    &history[history.len() - 1..],
+                )
+            };
+            let allowed_guesses = Arc::new(
+                candidates
+                    .iter()
+                    .filter(|word| {
+                        hints.iter().all(|(guess, clues)| {
+                            clues.iter().zip(unsolved.iter()).all(
+                                |(clue, &unsolved)| {
+                                    !unsolved || word.uses_hints(*guess, *clue)
+                                },
+                            )
+                        })
+                    })
+                    .copied()
+                    .collect::<Vec<_>>(),
+            );
+            games
+                .iter_mut()
+                .filter(|game| game.hard_mode)
+                .for_each(|game| {
+                    game.allowed_guesses = allowed_guesses.clone()
+                });
+        }
 
-        Self { games }
+        let games = games.try_into().unwrap();
+
+        Self {
+            games,
+            initial_guesses: self.initial_guesses.clone(),
+            history,
+        }
     }
 
     // An iterator that returns the results of repeated application of
     // a strategy.  The last element of the iterator will return true
-    // for `GameState.is_finished`.
+    // for either `is_finished` or `is_lost`.
+    #[allow(clippy::type_complexity)]
     pub fn simulate_strategy<'a, S: MultiStrategy<N, GAMES>>(
         &self,
         secret_words: [Word<N>; GAMES],
@@ -231,7 +649,7 @@ impl<const N: usize, const GAMES: usize> MultiGameState<N, GAMES> {
             Some(Ok((None, self.clone()))),
             move |res_state| {
                 if let Ok((_prev_clue, state)) = res_state {
-                    (!state.is_finished()).then(|| {
+                    (!state.is_finished() && !state.is_lost()).then(|| {
                         let guess = strategy.make_guess(state)?;
                         let clues = secret_words
                             .iter()
@@ -251,11 +669,18 @@ impl<const N: usize, const GAMES: usize> MultiGameState<N, GAMES> {
         )
     }
 
-    // Rough estimation of the difficulty of this quordle puzzle.  The
+    // The number of guesses allowed before the game is lost, following
+    // Dordle (7 guesses for 2 puzzles), Quordle (9 for 4), Octordle (13
+    // for 8), Sedecordle (21 for 16) and Duotrigordle (37 for 32).
+    pub fn default_max_guesses() -> usize {
+        GAMES + 5
+    }
+
+    // Rough estimation of the difficulty of this puzzle.  The
     // estimation is based on the number of secrets remaining in each
     // puzzle after having applied the correct guess from each other
-    // puzzle.
-    pub fn estimate_difficulty(&self, secret_words: [Word<N>; 4]) -> usize {
+    // puzzle.  Saturates at `usize::MAX` for large numbers of puzzles.
+    pub fn estimate_difficulty(&self, secret_words: [Word<N>; GAMES]) -> usize {
         secret_words
             .iter()
             .map(|guess| {
@@ -272,8 +697,8 @@ impl<const N: usize, const GAMES: usize> MultiGameState<N, GAMES> {
                 state
                     .games
                     .iter()
-                    .map(|game| game.possible_secrets.len())
-                    .product::<usize>()
+                    .map(|game| game.num_possible_secrets())
+                    .fold(1_usize, |acc, num| acc.saturating_mul(num))
             })
             .max()
             .unwrap()
@@ -297,9 +722,8 @@ impl<const N: usize, const GAMES: usize> MultiGameState<N, GAMES> {
         self.games
             .iter()
             .filter(|game| !game.is_finished())
-            .filter(|game| game.possible_secrets.len() == 1)
-            .map(|game| game.possible_secrets[0])
-            .next()
+            .filter(|game| game.num_possible_secrets() == 1)
+            .find_map(|game| game.possible_secrets().first().copied())
     }
 }
 
@@ -331,14 +755,263 @@ mod test {
             .iter()
             .map(|s| s.parse())
             .collect::<Result<_, _>>()?;
-        let before = GameState {
-            made_correct_guess: false,
-            allowed_guesses: secret.clone(),
-            possible_secrets: secret,
-        };
+        let before = GameState::new(secret.clone(), secret);
         let after = before.after_guess("chart".parse()?, "_G__G".parse()?);
 
-        assert_eq!(after.possible_secrets, vec!["ghost".parse()?]);
+        assert_eq!(after.possible_secrets().to_vec(), vec!["ghost".parse()?]);
+        assert!(std::ptr::eq(before.dictionary(), after.dictionary()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_clue_table_matches_comparison() -> Result<(), Error> {
+        let words: Vec<Word<5>> = ["apple", "table", "farts", "ghost"]
+            .iter()
+            .map(|s| s.parse())
+            .collect::<Result<_, _>>()?;
+        let without_table = GameState::new(words.clone(), words.clone());
+        let with_table = without_table.clone().with_clue_table();
+
+        for guess in words.iter() {
+            assert_eq!(
+                with_table.clue_ids(guess).collect::<Vec<_>>(),
+                without_table.clue_ids(guess).collect::<Vec<_>>()
+            );
+        }
+
+        let guess = "table".parse()?;
+        let clue = "_Y_GG".parse()?;
+        assert_eq!(
+            with_table
+                .after_guess(guess, clue)
+                .possible_secrets()
+                .to_vec(),
+            without_table
+                .after_guess(guess, clue)
+                .possible_secrets()
+                .to_vec(),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_secret_weights() -> Result<(), Error> {
+        use crate::strategy::AlphabeticalOrder;
+
+        let weights = [("table", 5.0), ("ghost", 2.0), ("farts", 3.0)]
+            .iter()
+            .map(|(word, weight)| Ok((word.parse()?, *weight)))
+            .collect::<Result<HashMap<Word<5>, f64>, Error>>()?;
+        let state = GameState::<5>::from_iter(
+            ["apple", "table", "farts", "ghost"].iter().copied(),
+        )
+        .with_weights(&weights);
+
+        assert_eq!(state.secret_weight(&"apple".parse()?), Some(2.0));
+        assert_eq!(state.total_weight(), 12.0);
+        assert_eq!(AlphabeticalOrder.make_guess(&state)?, "table".parse()?);
+
+        let after = state.after_guess("chart".parse()?, "__YYY".parse()?);
+        assert_eq!(after.possible_secrets().to_vec(), vec!["farts".parse()?]);
+        assert_eq!(after.secret_weights().collect::<Vec<_>>(), vec![3.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_secret_weights() -> Result<(), Error> {
+        use crate::strategy::MiniMax;
+
+        let state = GameState::<5>::from_iter(
+            ["apple", "table", "farts", "ghost"].iter().copied(),
+        );
+        let weights_of = |weights: &[(&str, f64)]| {
+            weights
+                .iter()
+                .map(|(word, weight)| Ok((word.parse()?, *weight)))
+                .collect::<Result<HashMap<Word<5>, f64>, Error>>()
+        };
+
+        // With every weight zero, all secrets are equally likely.
+        let all_zero = state
+            .clone()
+            .with_weights(&weights_of(&[("table", 0.0), ("ghost", 0.0)])?);
+        assert!(all_zero.secret_weights().all(|weight| weight == 1.0));
+        let tree = MiniMax.decision_tree(&all_zero)?;
+        assert_eq!(tree.weighted_mean_guesses(&all_zero), tree.mean_guesses());
+
+        // Secrets with a zero weight remain possible.
+        let some_zero =
+            state.with_weights(&weights_of(&[("table", 0.0), ("ghost", 2.0)])?);
+        assert!(some_zero.secret_weights().all(|weight| weight == 2.0));
+        let report = some_zero.explain(&"crane".parse()?);
+        assert!(report
+            .buckets
+            .iter()
+            .all(|bucket| bucket.weight == 2.0 * bucket.num_secrets as f64));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("crane", "_YG__", "grasp", true)]
+    #[case("crane", "_YG__", "trash", true)]
+    #[case("crane", "_YG__", "marry", false)]
+    #[case("crane", "_YG__", "blast", false)]
+    #[case("eerie", "Y___G", "elope", true)]
+    #[case("eerie", "Y___G", "bathe", false)]
+    fn test_uses_hints(
+        #[case] guess: &str,
+        #[case] clue: &str,
+        #[case] next_guess: &str,
+        #[case] expected: bool,
+    ) -> Result<(), Error> {
+        let guess: Word<5> = guess.parse()?;
+        let clue: Clue<5> = clue.parse()?;
+        let next_guess: Word<5> = next_guess.parse()?;
+        assert_eq!(next_guess.uses_hints(guess, clue), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_hard_mode_after_guess() -> Result<(), Error> {
+        let state = GameState::<5>::from_wordle().with_hard_mode(true);
+        let guess = "crane".parse()?;
+        let clue = "_YG__".parse()?;
+        let after = state.after_guess(guess, clue);
+
+        assert!(after.allowed_guesses().len() < state.allowed_guesses().len());
+        assert!(after
+            .allowed_guesses()
+            .iter()
+            .all(|word| word.uses_hints(guess, clue)));
+        assert!(after
+            .possible_secrets()
+            .iter()
+            .copied()
+            .all(|secret| after.allowed_guesses().contains(&secret)));
+        assert!(after.is_allowed_guess(&"grasp".parse()?));
+        assert!(!after.is_allowed_guess(&"blast".parse()?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_hard_mode_ignores_solved_boards() -> Result<(), Error> {
+        let single = GameState::<5>::from_wordle().with_hard_mode(true);
+        let state = MultiGameState::<5, 2>::new(single);
+        let ghost: Word<5> = "ghost".parse()?;
+        let crane: Word<5> = "crane".parse()?;
+        let shout: Word<5> = "shout".parse()?;
+
+        // While the first board is open, its hints restrict guesses.
+        let after_shout = state.after_guess(
+            shout,
+            [
+                ghost.compare_with_guess(shout),
+                crane.compare_with_guess(shout),
+            ],
+        );
+        assert!(after_shout.is_allowed_guess(&ghost));
+        assert!(!after_shout.is_allowed_guess(&crane));
+
+        // Once it is solved, only the second board's hints apply.
+        let after_ghost = after_shout.after_guess(
+            ghost,
+            [
+                ghost.compare_with_guess(ghost),
+                crane.compare_with_guess(ghost),
+            ],
+        );
+        assert!(after_ghost.games[0].is_finished());
+        assert!(!after_ghost.games[1].is_finished());
+        assert!(after_ghost.is_allowed_guess(&crane));
+        assert!(after_ghost.games[1].allowed_guesses().len() > 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_guesses() -> Result<(), Error> {
+        use crate::strategy::AlphabeticalOrder;
+
+        let state = GameState::<5>::from_wordle().with_max_guesses(1);
+        let secret = "zesty".parse()?;
+        let (_guess, last) = state
+            .simulate_strategy(secret, &AlphabeticalOrder)
+            .last()
+            .unwrap()?;
+        assert!(last.is_lost());
+        assert!(!last.is_finished());
+        assert_eq!(last.num_guesses, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_adversarial_clue() -> Result<(), Error> {
+        use crate::strategy::MiniMax;
+
+        let state = GameState::<5>::from_wordle();
+        let guess = "crane".parse()?;
+        let clue = state.adversarial_clue(&guess)?;
+        let largest = state
+            .partition(&guess)
+            .iter()
+            .map(|(_clue, child)| child.num_possible_secrets())
+            .max();
+        assert_eq!(clue, "_____".parse()?);
+        assert_eq!(
+            Some(state.after_guess(guess, clue).num_possible_secrets()),
+            largest
+        );
+
+        let words = state.possible_secrets()[..200].to_vec();
+        let state = GameState::new(words.clone(), words);
+        let (_guess, last) =
+            state.simulate_against_adversary(&MiniMax).last().unwrap()?;
+        assert!(last.is_finished());
+        Ok(())
+    }
+
+    #[test]
+    fn test_adversarial_tie_break() -> Result<(), Error> {
+        // Every clue keeps a single secret, so the host prefers the
+        // clue with the fewest correct tiles.
+        let state = GameState::<5>::from_iter(
+            ["crane", "droll", "disco"].iter().copied(),
+        );
+        let guess = "crane".parse()?;
+        assert!(state
+            .partition(&guess)
+            .iter()
+            .all(|(_clue, child)| child.num_possible_secrets() == 1));
+
+        let clue = state.adversarial_clue(&guess)?;
+        assert!(!clue.all_correct());
+        assert_eq!(clue, "Y____".parse()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_max_guesses() {
+        assert_eq!(MultiGameState::<5, 2>::default_max_guesses(), 7);
+        assert_eq!(MultiGameState::<5, 4>::default_max_guesses(), 9);
+        assert_eq!(MultiGameState::<5, 8>::default_max_guesses(), 13);
+        assert_eq!(MultiGameState::<5, 16>::default_max_guesses(), 21);
+        assert_eq!(MultiGameState::<5, 32>::default_max_guesses(), 37);
+    }
+
+    #[rstest]
+    #[case("eerie", "Y___G", true)]
+    #[case("eerie", "_Y__G", false)]
+    #[case("crane", "YYYYY", true)]
+    #[case("aaaaa", "YGGGG", false)]
+    #[case("abbey", "_YY__", true)]
+    #[case("abbey", "_YYGG", false)]
+    fn test_clue_is_possible(
+        #[case] guess: &str,
+        #[case] clue: &str,
+        #[case] expected: bool,
+    ) -> Result<(), Error> {
+        let guess: Word<5> = guess.parse()?;
+        let clue: Clue<5> = clue.parse()?;
+        assert_eq!(clue.is_possible_for(guess), expected);
         Ok(())
     }
 
@@ -1,6 +1,27 @@
 mod gameplay;
 pub use gameplay::*;
 
+mod clue_table;
+pub use clue_table::*;
+
+mod dictionary;
+pub use dictionary::*;
+
+mod decision_tree;
+pub use decision_tree::*;
+
+mod diagnosis;
+pub use diagnosis::*;
+
+mod explain;
+pub use explain::*;
+
+mod share;
+pub use share::*;
+
+mod notation;
+pub use notation::*;
+
 pub mod strategy;
 pub use strategy::{MultiStrategy, Strategy};
 
@@ -11,4 +32,3 @@ mod operators;
 mod tofrom_string;
 
 mod utils;
-pub use utils::*;
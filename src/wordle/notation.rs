@@ -0,0 +1,187 @@
+use super::{Clue, Error, Tile};
+
+use std::convert::TryInto;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// A way of writing the tiles of a clue, listed as (correct, wrong
+// position, not present).  Parsing a clue with `str::parse` accepts
+// any notation, so that clues can be pasted from other tools.
+// Displaying a clue uses `Letters`, and `Clue::display_in` displays
+// it in another notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClueNotation {
+    // G, Y and _, also accepting lowercase and B, X or . as absent.
+    Letters,
+
+    // g, y and b.
+    Lowercase,
+
+    // 2, 1 and 0.
+    Digits,
+
+    // +, ? and -, as used by several other solvers.
+    Symbols,
+
+    // The colored squares of a shared result, also accepting the
+    // high-contrast colors and white squares.
+    Emoji,
+}
+
+impl ClueNotation {
+    pub const ALL: [ClueNotation; 5] = [
+        ClueNotation::Letters,
+        ClueNotation::Lowercase,
+        ClueNotation::Digits,
+        ClueNotation::Symbols,
+        ClueNotation::Emoji,
+    ];
+
+    pub fn tile_char(&self, tile: Tile) -> char {
+        match (self, tile) {
+            (Self::Letters, Tile::Correct) => 'G',
+            (Self::Letters, Tile::WrongPosition) => 'Y',
+            (Self::Letters, Tile::NotPresentInWord) => '_',
+            (Self::Lowercase, Tile::Correct) => 'g',
+            (Self::Lowercase, Tile::WrongPosition) => 'y',
+            (Self::Lowercase, Tile::NotPresentInWord) => 'b',
+            (Self::Digits, Tile::Correct) => '2',
+            (Self::Digits, Tile::WrongPosition) => '1',
+            (Self::Digits, Tile::NotPresentInWord) => '0',
+            (Self::Symbols, Tile::Correct) => '+',
+            (Self::Symbols, Tile::WrongPosition) => '?',
+            (Self::Symbols, Tile::NotPresentInWord) => '-',
+            (Self::Emoji, Tile::Correct) => '\u{1F7E9}',
+            (Self::Emoji, Tile::WrongPosition) => '\u{1F7E8}',
+            (Self::Emoji, Tile::NotPresentInWord) => '\u{2B1B}',
+        }
+    }
+
+    // Returns the tile written as `c` in this notation, if any.
+    pub fn parse_tile(&self, c: char) -> Option<Tile> {
+        match (self, c) {
+            (Self::Letters, 'G' | 'g') => Some(Tile::Correct),
+            (Self::Letters, 'Y' | 'y') => Some(Tile::WrongPosition),
+            (Self::Letters, '_' | 'B' | 'b' | 'X' | 'x' | '.') => {
+                Some(Tile::NotPresentInWord)
+            }
+            (Self::Emoji, '\u{1F7E7}') => Some(Tile::Correct),
+            (Self::Emoji, '\u{1F7E6}') => Some(Tile::WrongPosition),
+            (Self::Emoji, '\u{2B1C}') => Some(Tile::NotPresentInWord),
+            _ => [Tile::Correct, Tile::WrongPosition, Tile::NotPresentInWord]
+                .iter()
+                .copied()
+                .find(|&tile| self.tile_char(tile) == c),
+        }
+    }
+
+    // Parses a clue written only in this notation.
+    pub fn parse_clue<const N: usize>(
+        &self,
+        s: &str,
+    ) -> Result<Clue<N>, Error> {
+        parse_clue_with(s, |c| self.parse_tile(c))
+    }
+
+    pub fn format_clue<const N: usize>(&self, clue: &Clue<N>) -> String {
+        clue.iter().map(|&tile| self.tile_char(tile)).collect()
+    }
+}
+
+// Parses a clue, using `parse_tile` to read each tile.
+pub(crate) fn parse_clue_with<const N: usize>(
+    s: &str,
+    parse_tile: impl Fn(char) -> Option<Tile>,
+) -> Result<Clue<N>, Error> {
+    let tiles = s
+        .chars()
+        // Emoji may be followed by a variation selector, which does not
+        // change the tile.
+        .filter(|&c| c != '\u{FE0F}')
+        .map(|c| parse_tile(c).ok_or(Error::NotTileChar(c)))
+        .collect::<Result<Vec<Tile>, Error>>()?
+        .as_slice()
+        .try_into()
+        .map_err(|_| Error::IncorrectStringLength)?;
+    Ok(Clue { tiles })
+}
+
+// A clue displayed in a chosen notation, as returned by
+// `Clue::display_in`.
+pub struct NotatedClue<const N: usize> {
+    clue: Clue<N>,
+    notation: ClueNotation,
+}
+
+impl<const N: usize> Clue<N> {
+    pub fn display_in(&self, notation: ClueNotation) -> NotatedClue<N> {
+        NotatedClue {
+            clue: *self,
+            notation,
+        }
+    }
+}
+
+impl<const N: usize> Display for NotatedClue<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.notation.format_clue(&self.clue))
+    }
+}
+
+impl Display for ClueNotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let name = match self {
+            Self::Letters => "letters",
+            Self::Lowercase => "lowercase",
+            Self::Digits => "digits",
+            Self::Symbols => "symbols",
+            Self::Emoji => "emoji",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for ClueNotation {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|notation| notation.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| Error::InvalidString(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("GY_Y_")]
+    #[case("gy.yx")]
+    #[case("gybyb")]
+    #[case("21010")]
+    #[case("+?-?-")]
+    #[case("\u{1F7E9}\u{1F7E8}\u{2B1B}\u{1F7E8}\u{2B1C}")]
+    fn test_parse_any_notation(#[case] s: &str) -> Result<(), Error> {
+        let clue: Clue<5> = s.parse()?;
+        assert_eq!(ClueNotation::Letters.format_clue(&clue), "GY_Y_");
+        Ok(())
+    }
+
+    #[test]
+    fn test_notation_round_trip() -> Result<(), Error> {
+        let clue: Clue<5> = "GY_Y_".parse()?;
+        ClueNotation::ALL.iter().try_for_each(|notation| {
+            let s = clue.display_in(*notation).to_string();
+            assert_eq!(notation.parse_clue::<5>(&s)?, clue);
+            assert_eq!(s.parse::<Clue<5>>()?, clue);
+            assert_eq!(
+                notation.to_string().parse::<ClueNotation>()?,
+                *notation
+            );
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,181 @@
+use super::{Clue, ClueNotation, Error, Tile};
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+impl<const N: usize> Clue<N> {
+    // Renders the clue as the emoji shown when sharing a result.
+    // Absent tiles are black in dark mode, and white otherwise.
+    pub fn to_emoji(&self, dark_mode: bool) -> String {
+        self.iter()
+            .map(|&tile| match tile {
+                Tile::NotPresentInWord if !dark_mode => '\u{2B1C}',
+                _ => ClueNotation::Emoji.tile_char(tile),
+            })
+            .collect()
+    }
+}
+
+// The block of text shared after a game, such as
+//
+//   Wordle 123 4/6*
+//
+//   ⬛🟨⬛⬛⬛
+//   ...
+//
+// The header gives the puzzle, the number of guesses taken (or X if
+// the game was lost), the guess limit, and a trailing `*` in hard
+// mode.  Only the clues are shared, not the guesses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareText<const N: usize> {
+    pub puzzle: String,
+    pub clues: Vec<Clue<N>>,
+    pub max_guesses: usize,
+    pub hard_mode: bool,
+    pub dark_mode: bool,
+}
+
+impl<const N: usize> ShareText<N> {
+    pub fn new(clues: Vec<Clue<N>>) -> Self {
+        Self {
+            puzzle: "".to_string(),
+            clues,
+            max_guesses: 6,
+            hard_mode: false,
+            dark_mode: true,
+        }
+    }
+
+    // The puzzle number, or any other name for the puzzle.
+    pub fn with_puzzle(self, puzzle: &str) -> Self {
+        Self {
+            puzzle: puzzle.to_string(),
+            ..self
+        }
+    }
+
+    pub fn with_max_guesses(self, max_guesses: usize) -> Self {
+        Self {
+            max_guesses,
+            ..self
+        }
+    }
+
+    pub fn with_hard_mode(self, hard_mode: bool) -> Self {
+        Self { hard_mode, ..self }
+    }
+
+    pub fn with_dark_mode(self, dark_mode: bool) -> Self {
+        Self { dark_mode, ..self }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.clues.last().is_some_and(|clue| clue.all_correct())
+    }
+}
+
+impl<const N: usize> Display for ShareText<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let score = if self.is_solved() {
+            self.clues.len().to_string()
+        } else {
+            "X".to_string()
+        };
+        let title = ["Wordle", &self.puzzle]
+            .iter()
+            .filter(|part| !part.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+        writeln!(
+            f,
+            "{} {}/{}{}",
+            title,
+            score,
+            self.max_guesses,
+            if self.hard_mode { "*" } else { "" }
+        )?;
+        writeln!(f)?;
+        self.clues.iter().try_for_each(|clue| {
+            writeln!(f, "{}", clue.to_emoji(self.dark_mode))
+        })
+    }
+}
+
+impl<const N: usize> FromStr for ShareText<N> {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut lines = s
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .peekable();
+
+        let header = lines
+            .next_if(|line| line.starts_with("Wordle"))
+            .map(parse_header)
+            .transpose()?;
+
+        let clues = lines
+            .map(|line| {
+                line.parse().map_err(|_| {
+                    Error::InvalidShareText(format!("Not a clue: {}", line))
+                })
+            })
+            .collect::<Result<Vec<Clue<N>>, Error>>()?;
+        if clues.is_empty() {
+            return Err(Error::InvalidShareText("No clues listed".to_string()));
+        }
+
+        let share = Self::new(clues);
+        let share = match header {
+            Some((puzzle, max_guesses, hard_mode)) => share
+                .with_puzzle(&puzzle)
+                .with_max_guesses(max_guesses)
+                .with_hard_mode(hard_mode),
+            None => share,
+        };
+        let dark_mode = !s.contains('\u{2B1C}');
+        Ok(share.with_dark_mode(dark_mode))
+    }
+}
+
+// Parses a header such as "Wordle 1,234 4/6*", returning the puzzle,
+// guess limit and whether hard mode was used.
+fn parse_header(line: &str) -> Result<(String, usize, bool), Error> {
+    let invalid =
+        || Error::InvalidShareText(format!("Invalid header: {}", line));
+    let tokens = line.split_whitespace().collect::<Vec<_>>();
+    let (score, puzzle) = tokens[1..].split_last().ok_or_else(invalid)?;
+
+    let hard_mode = score.ends_with('*');
+    let (_num_guesses, max_guesses) = score
+        .trim_end_matches('*')
+        .split_once('/')
+        .ok_or_else(invalid)?;
+    let max_guesses = max_guesses.parse().map_err(|_| invalid())?;
+    Ok((puzzle.join(" "), max_guesses, hard_mode))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_share_round_trip() -> Result<(), Error> {
+        let clues = vec!["_Y___".parse()?, "G_YY_".parse()?, "GGGGG".parse()?];
+        let share = ShareText::<5>::new(clues)
+            .with_puzzle("1,234")
+            .with_hard_mode(true);
+        let text = share.to_string();
+        assert!(text.starts_with("Wordle 1,234 3/6*\n\n"));
+        assert_eq!(text.parse::<ShareText<5>>()?, share);
+
+        let pasted = "Wordle 1,234 X/6\n\n\
+                      \u{2B1C}\u{1F7E6}\u{2B1C}\u{2B1C}\u{2B1C}\n";
+        let share = pasted.parse::<ShareText<5>>()?;
+        assert_eq!(share.clues, vec!["_Y___".parse()?]);
+        assert!(!share.is_solved() && !share.dark_mode);
+        Ok(())
+    }
+}
@@ -1,28 +1,29 @@
 use super::HeuristicStrategy;
-use crate::{Clue, GameState, Word};
+use crate::{GameState, Word};
 
-use std::cmp::Reverse;
+use std::borrow::Cow;
+use std::cmp::{Ordering, Reverse};
 
 use itertools::Itertools;
 
-// Make whatever guess results has the best worst-case scenario.
+// Make whatever guess results has the best worst-case scenario.  If
+// the secrets are weighted, the worst case is the clue with the
+// largest total weight.
 pub struct MiniMax;
 
 impl<const N: usize> HeuristicStrategy<N> for MiniMax {
-    type Output = usize;
+    type Output = OrderedFloat;
     fn heuristic(&self, state: &GameState<N>, guess: &Word<N>) -> Self::Output {
-        let mut counts = vec![0; Clue::<N>::num_clues()];
-        state.possible_secrets.iter().for_each(|secret| {
-            let clue = secret.compare_with_guess(*guess);
-            counts[clue.id()] += 1;
-        });
-
-        let max_counts: usize = *counts.iter().max().unwrap();
-        max_counts
+        state
+            .clue_weights(guess)
+            .into_iter()
+            .map(OrderedFloat)
+            .max()
+            .unwrap()
     }
 
     fn fmt(&self, heuristic: &Self::Output) -> String {
-        format!("{heuristic}")
+        format!("{}", heuristic.0)
     }
 }
 
@@ -33,32 +34,33 @@ pub struct MinimizeMean;
 impl<const N: usize> HeuristicStrategy<N> for MinimizeMean {
     type Output = Reverse<usize>;
     fn heuristic(&self, state: &GameState<N>, guess: &Word<N>) -> Self::Output {
-        Reverse(
-            state
-                .possible_secrets
-                .iter()
-                .map(|secret| secret.compare_with_guess(*guess))
-                .unique()
-                .count(),
-        )
+        Reverse(state.clue_ids(guess).unique().count())
+    }
+
+    fn fmt(&self, heuristic: &Self::Output) -> String {
+        format!("{} clues", heuristic.0)
     }
 }
 
 // Make whatever guess has the most possible clues, which minimizes
-// the average size of the next generation's solution space.
+// the average size of the next generation's solution space.  Uses
+// the total weight of each clue if the secrets are weighted.
 pub struct MinimizeSumSquares;
 
 impl<const N: usize> HeuristicStrategy<N> for MinimizeSumSquares {
-    type Output = usize;
+    type Output = OrderedFloat;
     fn heuristic(&self, state: &GameState<N>, guess: &Word<N>) -> Self::Output {
-        state
-            .possible_secrets
-            .iter()
-            .map(|secret| secret.compare_with_guess(*guess))
-            .counts()
-            .into_values()
-            .map(|c| c * c)
-            .sum::<usize>()
+        OrderedFloat(
+            state
+                .clue_weights(guess)
+                .into_iter()
+                .map(|weight| weight * weight)
+                .sum::<f64>(),
+        )
+    }
+
+    fn fmt(&self, heuristic: &Self::Output) -> String {
+        format!("{}", heuristic.0)
     }
 }
 
@@ -71,31 +73,91 @@ impl<const N: usize> HeuristicStrategy<N> for EarlyGuesses {
     fn heuristic(&self, state: &GameState<N>, guess: &Word<N>) -> Self::Output {
         Reverse(
             state
-                .possible_secrets
-                .iter()
-                .map(|secret| secret.compare_with_guess(*guess))
+                .clue_ids(guess)
                 .counts()
                 .into_values()
                 .filter(|&counts| counts == 1)
                 .count(),
         )
     }
+
+    fn fmt(&self, heuristic: &Self::Output) -> String {
+        format!("{} solved next turn", heuristic.0)
+    }
+}
+
+// Wrapper to provide a total ordering for floating-point heuristics,
+// since `HeuristicStrategy::Output` must be `Ord`.
+#[derive(Debug, Clone, Copy)]
+pub struct OrderedFloat(pub f64);
+
+impl PartialEq for OrderedFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedFloat {}
+
+impl PartialOrd for OrderedFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-// Guess the first secret worst that is still possible.
+impl Ord for OrderedFloat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+// Make whatever guess has the highest Shannon entropy of its clue
+// distribution, which maximizes the expected information gained.
+// Weighted secrets contribute in proportion to their weight.
+pub struct MaximizeEntropy;
+
+impl<const N: usize> HeuristicStrategy<N> for MaximizeEntropy {
+    type Output = Reverse<OrderedFloat>;
+    fn heuristic(&self, state: &GameState<N>, guess: &Word<N>) -> Self::Output {
+        let total = state.total_weight();
+        let entropy = state
+            .clue_weights(guess)
+            .into_iter()
+            .filter(|&weight| weight > 0.0)
+            .map(|weight| {
+                let p = weight / total;
+                -p * p.log2()
+            })
+            .sum::<f64>();
+        Reverse(OrderedFloat(entropy))
+    }
+
+    fn fmt(&self, heuristic: &Self::Output) -> String {
+        format!("{:.4} bits", (heuristic.0).0)
+    }
+}
+
+// Guess the first secret worst that is still possible.  If the
+// secrets are weighted, guess the most likely secret, using
+// alphabetical order to break ties.
 pub struct AlphabeticalOrder;
 
 impl<const N: usize> HeuristicStrategy<N> for AlphabeticalOrder {
-    type Output = Word<N>;
-    fn heuristic(
-        &self,
-        _state: &GameState<N>,
-        guess: &Word<N>,
-    ) -> Self::Output {
-        *guess
+    type Output = (Reverse<OrderedFloat>, Word<N>);
+    fn heuristic(&self, state: &GameState<N>, guess: &Word<N>) -> Self::Output {
+        let weight = if state.is_weighted() {
+            state.secret_weight(guess).unwrap_or(0.0)
+        } else {
+            1.0
+        };
+        (Reverse(OrderedFloat(weight)), *guess)
     }
 
-    fn word_options<'a>(&self, state: &'a GameState<N>) -> &'a Vec<Word<N>> {
-        &state.possible_secrets
+    fn word_options<'a>(&self, state: &'a GameState<N>) -> Cow<'a, [Word<N>]> {
+        Cow::Borrowed(state.possible_secrets())
+    }
+
+    fn fmt(&self, heuristic: &Self::Output) -> String {
+        format!("weight {}", ((heuristic.0).0).0)
     }
 }
@@ -9,6 +9,14 @@ pub use impls::*;
 mod multi_impls;
 pub use multi_impls::*;
 
+mod optimal;
+pub use optimal::*;
+
+mod worst_case;
+pub use worst_case::*;
+
+pub(crate) mod search;
+
 pub fn all_strategies<const N: usize>() -> HashMap<String, Box<dyn Strategy<N>>>
 {
     let mut strategies: HashMap<String, Box<dyn Strategy<N>>> = HashMap::new();
@@ -23,11 +31,39 @@ pub fn all_strategies<const N: usize>() -> HashMap<String, Box<dyn Strategy<N>>>
     define_strategy!(MinimizeMean);
     define_strategy!(MinimizeSumSquares);
     define_strategy!(EarlyGuesses);
+    define_strategy!(MaximizeEntropy);
     define_strategy!(AlphabeticalOrder);
 
     strategies
 }
 
+// Strategies that search over decision trees.  These are too slow to
+// run by default on the full word lists, and are only used when
+// requested by name.
+pub fn search_strategies<const N: usize>(
+) -> HashMap<String, Box<dyn Strategy<N>>> {
+    let mut strategies: HashMap<String, Box<dyn Strategy<N>>> = HashMap::new();
+
+    strategies.insert(
+        "OptimalDecisionTree".to_string(),
+        Box::new(OptimalDecisionTree::new().with_max_candidates(10)),
+    );
+    strategies.insert(
+        "BoundedWorstCase".to_string(),
+        Box::new(OptimalWorstCase::new().with_max_candidates(10)),
+    );
+
+    strategies
+}
+
+// Every strategy that may be requested by name.
+pub fn named_strategies<const N: usize>(
+) -> HashMap<String, Box<dyn Strategy<N>>> {
+    let mut strategies = all_strategies();
+    strategies.extend(search_strategies());
+    strategies
+}
+
 pub fn all_multi_strategies<const N: usize, const GAMES: usize>(
 ) -> HashMap<String, Box<dyn MultiStrategy<N, GAMES>>> {
     let mut strategies: HashMap<String, Box<dyn MultiStrategy<N, GAMES>>> =
@@ -49,6 +85,7 @@ pub fn all_multi_strategies<const N: usize, const GAMES: usize>(
             composed_strategy!($meta_cls, MinimizeMean);
             composed_strategy!($meta_cls, MinimizeSumSquares);
             composed_strategy!($meta_cls, EarlyGuesses);
+            composed_strategy!($meta_cls, MaximizeEntropy);
             composed_strategy!($meta_cls, AlphabeticalOrder);
         };
     }
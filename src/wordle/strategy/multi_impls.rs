@@ -24,12 +24,12 @@ impl<S: Strategy<N>, const N: usize, const GAMES: usize> MultiStrategy<N, GAMES>
         &self,
         state: &MultiGameState<N, GAMES>,
     ) -> Result<Word<N>, Error> {
-        Ok(state
+        state
             .games
             .iter()
             .find(|game| !game.is_finished())
             .map(|game| self.single.make_guess(game))
-            .ok_or(Error::NoWordsRemaining)??)
+            .ok_or(Error::NoWordsRemaining)?
     }
 }
 
@@ -58,11 +58,11 @@ impl<S: Strategy<N>, const N: usize, const GAMES: usize> MultiStrategy<N, GAMES>
                     .games
                     .iter()
                     .filter(|game| !game.is_finished())
-                    .max_by_key(|game| game.possible_secrets.len())
+                    .max_by_key(|game| game.num_possible_secrets())
                     .map(|game| self.single.make_guess(game))
                     .unwrap()
             },
-            |guess| Ok(guess),
+            Ok,
         )
     }
 }
@@ -90,28 +90,25 @@ impl<S: HeuristicStrategy<N>, const N: usize, const GAMES: usize>
                 let option_set = state
                     .games
                     .iter()
-                    .map(|game| game.possible_secrets.iter())
-                    .flatten()
+                    .flat_map(|game| game.possible_secrets().iter().copied())
                     .collect::<HashSet<_>>();
 
                 state
                     .games
                     .iter()
-                    .map(|game| game.allowed_guesses.iter())
-                    .flatten()
+                    .flat_map(|game| game.allowed_guesses().iter())
                     .collect::<HashSet<_>>()
                     .into_iter()
                     .min_by_key(|guess| {
-                        let ret = (
+                        (
                             self.multi_heuristic(state, guess),
                             !option_set.contains(guess),
-                        );
-                        ret
+                        )
                     })
-                    .map(|x| *x)
+                    .copied()
                     .ok_or(Error::NoWordsRemaining)
             },
-            |guess| Ok(guess),
+            Ok,
         )
     }
 }
@@ -0,0 +1,419 @@
+use super::search::{self, StateKey};
+use super::Strategy;
+use crate::{Error, GameState, Word};
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use itertools::Itertools;
+
+// Make whichever guess begins the decision tree with the lowest mean
+// number of guesses over all possible secrets.  Uses a depth-limited
+// search with branch-and-bound pruning.  Solved subtrees are cached,
+// so that later calls to `make_guess` follow the same tree.
+//
+// The search is exact unless `max_candidates` is set, in which case
+// only the most promising guesses at each node are searched.
+pub struct OptimalDecisionTree<const N: usize> {
+    max_depth: usize,
+    max_candidates: Option<usize>,
+
+    // Exact solutions, as (total guesses, best guess), keyed by the
+    // state and the number of guesses remaining.
+    solutions: Mutex<HashMap<(StateKey<N>, usize), (usize, Word<N>)>>,
+
+    // Lower bounds on the total guesses for searches that were
+    // pruned before finding a solution.
+    lower_bounds: Mutex<HashMap<(StateKey<N>, usize), usize>>,
+
+    // The guess to make for each state in the most recently solved
+    // tree, keyed by the state and the number of guesses remaining.
+    plan: Mutex<HashMap<(StateKey<N>, usize), Word<N>>>,
+}
+
+impl<const N: usize> OptimalDecisionTree<N> {
+    pub fn new() -> Self {
+        Self {
+            max_depth: 6,
+            max_candidates: None,
+            solutions: Mutex::new(HashMap::new()),
+            lower_bounds: Mutex::new(HashMap::new()),
+            plan: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // The maximum number of guesses allowed to solve any secret.
+    pub fn with_max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    // The number of guesses searched at each node, ordered by the
+    // lower bound on their total number of guesses.
+    pub fn with_max_candidates(self, max_candidates: usize) -> Self {
+        Self {
+            max_candidates: Some(max_candidates),
+            ..self
+        }
+    }
+
+    // Returns the total number of guesses, summed over all possible
+    // secrets, required by the optimal decision tree.  Returns None
+    // if no tree can solve every secret within the depth limit.
+    pub fn total_guesses(&self, state: &GameState<N>) -> Option<usize> {
+        self.solve(state, self.max_depth, usize::MAX)
+            .map(|(total, _guess)| total)
+    }
+
+    // Lower bound on the total number of guesses required to solve
+    // `num_secrets` secrets.  At most one secret can be solved by the
+    // next guess, and all others require at least one more.
+    fn lower_bound(num_secrets: usize) -> usize {
+        (2 * num_secrets).saturating_sub(1)
+    }
+
+    // Returns the optimal (total guesses, guess) for the state, if it
+    // is less than `beta`.  Otherwise, returns None.
+    fn solve(
+        &self,
+        state: &GameState<N>,
+        depth: usize,
+        beta: usize,
+    ) -> Option<(usize, Word<N>)> {
+        let num_secrets = state.num_possible_secrets();
+        if num_secrets == 0 || depth == 0 || (num_secrets > 1 && depth == 1) {
+            return None;
+        }
+        if num_secrets <= 2 {
+            let total = Self::lower_bound(num_secrets);
+            let guess = state.possible_secrets()[0];
+            return (total < beta).then_some((total, guess));
+        }
+
+        let key = (search::state_key(state), depth);
+        if let Some(&solution) = self.solutions.lock().unwrap().get(&key) {
+            return (solution.0 < beta).then_some(solution);
+        }
+        if let Some(&bound) = self.lower_bounds.lock().unwrap().get(&key) {
+            if bound >= beta {
+                return None;
+            }
+        }
+
+        let node_lower_bound = Self::lower_bound(num_secrets);
+        let mut best = None;
+        let mut bound = beta;
+        for (guess_lower_bound, guess) in self.candidates(state) {
+            if guess_lower_bound >= bound {
+                break;
+            }
+            if let Some(total) = self.evaluate_guess(state, guess, depth, bound)
+            {
+                bound = total;
+                best = Some((total, guess));
+                if total == node_lower_bound {
+                    break;
+                }
+            }
+        }
+
+        match best {
+            Some(solution) => {
+                self.solutions.lock().unwrap().insert(key, solution);
+            }
+            None => {
+                let mut lower_bounds = self.lower_bounds.lock().unwrap();
+                let entry = lower_bounds.entry(key).or_insert(beta);
+                *entry = (*entry).max(beta);
+            }
+        }
+
+        best
+    }
+
+    // Returns the total number of guesses if `guess` is made next, if
+    // it is less than `beta`.  Otherwise, returns None.
+    fn evaluate_guess(
+        &self,
+        state: &GameState<N>,
+        guess: Word<N>,
+        depth: usize,
+        beta: usize,
+    ) -> Option<usize> {
+        let children = state
+            .partition(&guess)
+            .into_iter()
+            .filter(|(clue, _child)| !clue.all_correct())
+            .map(|(_clue, child)| child)
+            .sorted_by_key(|child| {
+                std::cmp::Reverse(child.num_possible_secrets())
+            })
+            .collect::<Vec<_>>();
+
+        let mut total = state.num_possible_secrets()
+            + children
+                .iter()
+                .map(|child| Self::lower_bound(child.num_possible_secrets()))
+                .sum::<usize>();
+
+        for child in children.iter() {
+            if total >= beta {
+                return None;
+            }
+            let child_lower_bound =
+                Self::lower_bound(child.num_possible_secrets());
+            let child_beta = beta - (total - child_lower_bound);
+            let (child_total, _guess) =
+                self.solve(child, depth - 1, child_beta)?;
+            total += child_total - child_lower_bound;
+        }
+
+        (total < beta).then_some(total)
+    }
+
+    // Returns the guesses worth searching, along with a lower bound on
+    // the total number of guesses if each were made.
+    fn candidates(&self, state: &GameState<N>) -> Vec<(usize, Word<N>)> {
+        let num_secrets = state.num_possible_secrets();
+        search::candidates(state, self.max_candidates, |candidate| {
+            num_secrets
+                + candidate
+                    .clue_counts
+                    .iter()
+                    .filter(|(&id, _count)| id != 0)
+                    .map(|(_id, &count)| Self::lower_bound(count))
+                    .sum::<usize>()
+        })
+    }
+
+    // Record the guess for each state in the solved tree.
+    fn record_plan(&self, state: &GameState<N>, depth: usize) {
+        let key = (search::state_key(state), depth);
+        let solution = self.solutions.lock().unwrap().get(&key).copied();
+        if let Some((_total, guess)) = solution {
+            self.plan.lock().unwrap().insert(key, guess);
+            state
+                .partition(&guess)
+                .into_iter()
+                .filter(|(clue, _child)| !clue.all_correct())
+                .for_each(|(_clue, child)| self.record_plan(&child, depth - 1));
+        }
+    }
+}
+
+impl<const N: usize> Default for OptimalDecisionTree<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Strategy<N> for OptimalDecisionTree<N> {
+    fn make_guess(&self, state: &GameState<N>) -> Result<Word<N>, Error> {
+        // Only the guesses left in the game may be used, including for
+        // states outside the plan, such as after a guess other than
+        // the one suggested.
+        let depth = self.max_depth.saturating_sub(state.num_guesses);
+        if depth == 0 {
+            return Err(Error::NoSolutionWithinDepth);
+        }
+
+        if state.num_possible_secrets() <= 1 {
+            return state
+                .possible_secrets()
+                .first()
+                .copied()
+                .ok_or(Error::NoWordsRemaining);
+        }
+
+        let planned = self
+            .plan
+            .lock()
+            .unwrap()
+            .get(&(search::state_key(state), depth))
+            .copied();
+        if let Some(guess) = planned {
+            return Ok(guess);
+        }
+
+        let (_total, guess) = self
+            .solve(state, depth, usize::MAX)
+            .ok_or(Error::NoSolutionWithinDepth)?;
+        self.record_plan(state, depth);
+        Ok(guess)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::all_strategies;
+
+    fn small_state() -> GameState<5> {
+        let wordle = GameState::<5>::from_wordle();
+        let words = wordle.possible_secrets()[..100].to_vec();
+        GameState::new(words.clone(), words).with_clue_table()
+    }
+
+    #[test]
+    fn test_optimal_beats_heuristics() {
+        let state = small_state();
+        let optimal = OptimalDecisionTree::new();
+        let total = optimal.total_guesses(&state).unwrap();
+
+        let optimal_paths =
+            optimal.deterministic_strategy_results(state.clone());
+        assert_eq!(optimal_paths.len(), state.num_possible_secrets());
+        assert_eq!(
+            optimal_paths.iter().map(|path| path.len()).sum::<usize>(),
+            total
+        );
+
+        all_strategies::<5>()
+            .into_iter()
+            .for_each(|(name, strategy)| {
+                let heuristic_total = strategy
+                    .deterministic_strategy_results(state.clone())
+                    .iter()
+                    .map(|path| path.len())
+                    .sum::<usize>();
+                assert!(total <= heuristic_total, "{} beat optimal", name);
+            });
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let state = small_state();
+        let unlimited = OptimalDecisionTree::new().total_guesses(&state);
+        let too_shallow = OptimalDecisionTree::new()
+            .with_max_depth(2)
+            .total_guesses(&state);
+        assert!(unlimited.is_some());
+        assert!(too_shallow.is_none());
+    }
+
+    // Minimum total guesses by exhaustive search, without pruning.
+    fn brute_force_total(
+        state: &GameState<5>,
+        memo: &mut HashMap<Vec<Word<5>>, usize>,
+    ) -> usize {
+        let num_secrets = state.num_possible_secrets();
+        if num_secrets <= 1 {
+            return num_secrets;
+        }
+        let key = state.possible_secrets().to_vec();
+        if let Some(&total) = memo.get(&key) {
+            return total;
+        }
+        let total = state
+            .allowed_guesses()
+            .iter()
+            .filter_map(|guess| {
+                let children = state
+                    .partition(guess)
+                    .into_iter()
+                    .filter(|(clue, _child)| !clue.all_correct())
+                    .map(|(_clue, child)| child)
+                    .collect::<Vec<_>>();
+                let progress = children.len() > 1
+                    || children.iter().all(|child| {
+                        child.num_possible_secrets() < num_secrets
+                    });
+                progress.then_some(children)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|children| {
+                num_secrets
+                    + children
+                        .iter()
+                        .map(|child| brute_force_total(child, memo))
+                        .sum::<usize>()
+            })
+            .min()
+            .unwrap();
+        memo.insert(key, total);
+        total
+    }
+
+    #[test]
+    fn test_matches_exhaustive_search() {
+        let wordle = GameState::<5>::from_wordle();
+        let words = wordle.possible_secrets()[..30].to_vec();
+        let state = GameState::new(words.clone(), words);
+        assert_eq!(
+            OptimalDecisionTree::new().total_guesses(&state),
+            Some(brute_force_total(&state, &mut HashMap::new()))
+        );
+    }
+
+    #[test]
+    fn test_replan_respects_guesses_made() -> Result<(), Error> {
+        let state = small_state();
+        let secret = state.possible_secrets()[50];
+        let guess = state.allowed_guesses()[0];
+        let state = state.after_guess(guess, secret.compare_with_guess(guess));
+        assert_eq!(state.num_guesses, 1);
+
+        let depths = (1..=6)
+            .filter_map(|max_depth| {
+                let optimal =
+                    OptimalDecisionTree::new().with_max_depth(max_depth);
+                optimal
+                    .decision_tree(&state)
+                    .ok()
+                    .map(|tree| (max_depth, tree))
+            })
+            .map(|(max_depth, tree)| {
+                tree.verify(&state)?;
+                assert!(tree.depth() + state.num_guesses <= max_depth);
+                Ok(max_depth)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        assert!(!depths.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_respects_guesses_remaining() -> Result<(), Error> {
+        let state = small_state();
+        let optimal = OptimalDecisionTree::new();
+        let tree = optimal.decision_tree(&state)?;
+
+        // Reach the planned state again with fewer guesses remaining.
+        // The plan must not be replayed where a solver without one
+        // would find a tree within the guesses left.
+        (1..tree.depth()).try_for_each(|num_guesses| {
+            let mut later = state.clone();
+            later.num_guesses = num_guesses;
+            let replanned = optimal.decision_tree(&later);
+            let fresh = OptimalDecisionTree::new().decision_tree(&later);
+            assert_eq!(replanned.is_ok(), fresh.is_ok());
+            if let Ok(subtree) = replanned {
+                subtree.verify(&later)?;
+                assert!(subtree.depth() + num_guesses <= 6);
+            }
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_hard_mode_tree_is_valid() -> Result<(), Error> {
+        let state = small_state().with_hard_mode(true);
+        let optimal = OptimalDecisionTree::new();
+        let tree = optimal.decision_tree(&state)?;
+        tree.verify(&state)?;
+        assert_eq!(Some(tree.total_guesses()), optimal.total_guesses(&state));
+        Ok(())
+    }
+
+    // Known optimum for the wordle word lists is 7920 total guesses.
+    // A search limited to a few candidates at each node gives an upper
+    // bound on it.  Slow, so only run when requested.
+    #[test]
+    #[ignore]
+    fn test_wordle_bounded_search() {
+        let state = GameState::<5>::from_wordle().with_clue_table();
+        let strategy = OptimalDecisionTree::new().with_max_candidates(10);
+        let total = strategy.total_guesses(&state).unwrap();
+        assert!(7920 <= total);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{GameState, Word};
+
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+// Identifies a state for the caches of an exhaustive search.  Holds
+// the remaining secrets, along with the allowed guesses in hard mode,
+// as states with the same secrets may allow different guesses.
+pub(crate) type StateKey<const N: usize> = (Vec<Word<N>>, Vec<Word<N>>);
+
+pub(crate) fn state_key<const N: usize>(state: &GameState<N>) -> StateKey<N> {
+    let allowed_guesses = if state.hard_mode {
+        state.allowed_guesses().to_vec()
+    } else {
+        Vec::new()
+    };
+    (state.possible_secrets().to_vec(), allowed_guesses)
+}
+
+// Properties of a guess worth searching, including the number of
+// possible secrets that would give each clue, indexed by `Clue::id`.
+pub(crate) struct Candidate {
+    pub is_possible_secret: bool,
+    pub clue_counts: HashMap<usize, usize>,
+}
+
+// Returns the guesses worth searching, ordered by `score`, along with
+// their scores.  Guesses that provide no information are excluded.
+// If `max_candidates` is set, only that many guesses are returned, and
+// a search over them is no longer exhaustive.
+pub(crate) fn candidates<const N: usize, K: Ord>(
+    state: &GameState<N>,
+    max_candidates: Option<usize>,
+    score: impl Fn(&Candidate) -> K,
+) -> Vec<(K, Word<N>)> {
+    let candidates = state
+        .allowed_guesses()
+        .iter()
+        .copied()
+        .chain(state.possible_secrets().iter().copied())
+        .unique()
+        .filter_map(|guess| {
+            let candidate = Candidate {
+                is_possible_secret: state.is_possible_secret(&guess),
+                clue_counts: state.clue_ids(&guess).counts(),
+            };
+            (candidate.is_possible_secret || candidate.clue_counts.len() > 1)
+                .then(|| (score(&candidate), guess))
+        })
+        .sorted();
+
+    match max_candidates {
+        Some(max_candidates) => candidates.take(max_candidates).collect(),
+        None => candidates.collect(),
+    }
+}
@@ -1,43 +1,54 @@
-use crate::{Error, GameState, MultiGameState, Word};
+use crate::{DecisionTree, Error, GameState, MultiGameState, Word};
 
-use itertools::Itertools;
+use std::borrow::Cow;
 
-pub trait Strategy<const N: usize> {
+use rayon::prelude::*;
+
+// A guess considered by a strategy, along with its score.
+#[derive(Debug, Clone)]
+pub struct RankedGuess<const N: usize, T> {
+    pub guess: Word<N>,
+    pub score: T,
+    pub is_possible_secret: bool,
+}
+
+pub trait Strategy<const N: usize>: Send + Sync {
     fn make_guess(&self, state: &GameState<N>) -> Result<Word<N>, Error>;
 
+    // Returns up to `k` guesses, best first, with a description of
+    // each score.  The first guess is the one `make_guess` would make.
+    // Strategies that do not score guesses return only that guess.
+    fn ranked_guesses(
+        &self,
+        state: &GameState<N>,
+        _k: usize,
+    ) -> Result<Vec<RankedGuess<N, String>>, Error> {
+        let guess = self.make_guess(state)?;
+        Ok(vec![RankedGuess {
+            guess,
+            score: "".to_string(),
+            is_possible_secret: state.is_possible_secret(&guess),
+        }])
+    }
+
+    // Returns the decision tree resulting from application of a
+    // deterministic strategy.
+    fn decision_tree(
+        &self,
+        initial_state: &GameState<N>,
+    ) -> Result<DecisionTree<N>, Error> {
+        DecisionTree::from_strategy(self, initial_state)
+    }
+
     // Returns all possible sequences of guesses resulting from
     // application of a deterministic strategy.
     fn deterministic_strategy_results(
         &self,
         initial_state: GameState<N>,
     ) -> Vec<Vec<Word<N>>> {
-        let mut final_paths = Vec::new();
-        let mut stack = vec![(Vec::new(), initial_state)];
-
-        while !stack.is_empty() {
-            let (mut path, state) = stack.pop().unwrap();
-
-            let guess = self.make_guess(&state).unwrap();
-            path.push(guess);
-
-            state
-                .possible_secrets
-                .iter()
-                .map(|secret| secret.compare_with_guess(guess))
-                .unique()
-                .for_each(|clue| {
-                    if clue.all_correct() {
-                        final_paths.push(path.clone());
-                    } else {
-                        stack.push((
-                            path.clone(),
-                            state.after_guess(guess, clue),
-                        ))
-                    }
-                })
-        }
-
-        final_paths
+        self.decision_tree(&initial_state)
+            .unwrap()
+            .paths_from(&initial_state)
     }
 }
 
@@ -45,6 +56,21 @@ impl<const N: usize> Strategy<N> for Box<dyn Strategy<N>> {
     fn make_guess(&self, state: &GameState<N>) -> Result<Word<N>, Error> {
         self.as_ref().make_guess(state)
     }
+
+    fn ranked_guesses(
+        &self,
+        state: &GameState<N>,
+        k: usize,
+    ) -> Result<Vec<RankedGuess<N, String>>, Error> {
+        self.as_ref().ranked_guesses(state, k)
+    }
+
+    fn decision_tree(
+        &self,
+        initial_state: &GameState<N>,
+    ) -> Result<DecisionTree<N>, Error> {
+        self.as_ref().decision_tree(initial_state)
+    }
 }
 
 pub trait MultiStrategy<const N: usize, const GAMES: usize> {
@@ -65,29 +91,178 @@ impl<const N: usize, const GAMES: usize> MultiStrategy<N, GAMES>
     }
 }
 
-pub trait HeuristicStrategy<const N: usize> {
-    type Output: Ord;
+pub trait HeuristicStrategy<const N: usize>: Send + Sync {
+    type Output: Ord + Send;
     fn heuristic(&self, state: &GameState<N>, guess: &Word<N>) -> Self::Output;
-    fn word_options<'a>(&self, state: &'a GameState<N>) -> &'a Vec<Word<N>> {
-        &state.allowed_guesses
+    fn word_options<'a>(&self, state: &'a GameState<N>) -> Cow<'a, [Word<N>]> {
+        Cow::Borrowed(state.allowed_guesses())
     }
     fn fmt(&self, _heuristic: &Self::Output) -> String {
-        return "".to_string();
+        "".to_string()
+    }
+
+    // Returns the `k` guesses with the best heuristic, best first.
+    // Ties are broken in favor of the earliest word in `word_options`,
+    // as in `make_guess`.
+    fn top_guesses(
+        &self,
+        state: &GameState<N>,
+        k: usize,
+    ) -> Vec<RankedGuess<N, Self::Output>> {
+        let options = self.word_options(state);
+        let mut scores = options
+            .par_iter()
+            .enumerate()
+            .map(|(i, guess)| (self.heuristic(state, guess), i))
+            .collect::<Vec<_>>();
+        if k < scores.len() {
+            scores.select_nth_unstable(k);
+            scores.truncate(k);
+        }
+        scores.sort_unstable();
+        scores
+            .into_iter()
+            .map(|(score, i)| RankedGuess {
+                guess: options[i],
+                score,
+                is_possible_secret: state.is_possible_secret(&options[i]),
+            })
+            .collect()
     }
 }
 
 impl<H: HeuristicStrategy<N>, const N: usize> Strategy<N> for H {
     fn make_guess(&self, state: &GameState<N>) -> Result<Word<N>, Error> {
-        if state.possible_secrets.is_empty() {
-            Err(Error::NoWordsRemaining)
-        } else if state.possible_secrets.len() == 1 {
-            Ok(state.possible_secrets[0])
+        if state.num_possible_secrets() <= 1 {
+            state
+                .possible_secrets()
+                .first()
+                .copied()
+                .ok_or(Error::NoWordsRemaining)
         } else {
-            Ok(*self
-                .word_options(state)
+            // Scoring is done in parallel.  Including the index in the
+            // comparison breaks ties in favor of the earliest word,
+            // matching the behavior of a serial `min_by_key`.
+            let options = self.word_options(state);
+            options
+                .par_iter()
+                .enumerate()
+                .map(|(i, guess)| (self.heuristic(state, guess), i))
+                .min()
+                .map(|(_heuristic, i)| options[i])
+                .ok_or(Error::NoWordsRemaining)
+        }
+    }
+
+    fn ranked_guesses(
+        &self,
+        state: &GameState<N>,
+        k: usize,
+    ) -> Result<Vec<RankedGuess<N, String>>, Error> {
+        if state.num_possible_secrets() <= 1 {
+            let guess = self.make_guess(state)?;
+            return Ok(vec![RankedGuess {
+                guess,
+                score: "".to_string(),
+                is_possible_secret: true,
+            }]);
+        }
+        Ok(self
+            .top_guesses(state, k)
+            .into_iter()
+            .map(|ranked| RankedGuess {
+                guess: ranked.guess,
+                score: self.fmt(&ranked.score),
+                is_possible_secret: ranked.is_possible_secret,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::{EarlyGuesses, MiniMax};
+
+    use itertools::Itertools;
+
+    fn serial_guess<H: HeuristicStrategy<N>, const N: usize>(
+        strategy: &H,
+        state: &GameState<N>,
+    ) -> Word<N> {
+        *strategy
+            .word_options(state)
+            .iter()
+            .min_by_key(|guess| strategy.heuristic(state, guess))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parallel_matches_serial() -> Result<(), Error> {
+        let wordle = GameState::<5>::from_wordle();
+        let state = GameState::new(
+            wordle.allowed_guesses()[..2000].to_vec(),
+            wordle.possible_secrets()[..300].to_vec(),
+        );
+        let state = state.after_guess("crane".parse()?, "_____".parse()?);
+
+        assert_eq!(MiniMax.make_guess(&state)?, serial_guess(&MiniMax, &state));
+        assert_eq!(
+            EarlyGuesses.make_guess(&state)?,
+            serial_guess(&EarlyGuesses, &state)
+        );
+
+        let top = MiniMax.top_guesses(&state, 5);
+        assert_eq!(top.len(), 5);
+        assert_eq!(top[0].guess, MiniMax.make_guess(&state)?);
+        assert!(top.windows(2).all(|pair| pair[0].score <= pair[1].score));
+        assert!(top.iter().all(|ranked| ranked.is_possible_secret
+            == state.is_possible_secret(&ranked.guess)));
+        Ok(())
+    }
+
+    // The single-threaded walk previously used to produce the results
+    // of a deterministic strategy.
+    fn serial_results<S: Strategy<N>, const N: usize>(
+        strategy: &S,
+        initial_state: GameState<N>,
+    ) -> Vec<Vec<Word<N>>> {
+        let mut final_paths = Vec::new();
+        let mut stack = vec![(Vec::new(), initial_state)];
+
+        while let Some((mut path, state)) = stack.pop() {
+            let guess = strategy.make_guess(&state).unwrap();
+            path.push(guess);
+
+            state
+                .possible_secrets()
                 .iter()
-                .min_by_key(|guess| self.heuristic(state, guess))
-                .unwrap())
+                .map(|secret| secret.compare_with_guess(guess))
+                .unique()
+                .for_each(|clue| {
+                    if clue.all_correct() {
+                        final_paths.push(path.clone());
+                    } else {
+                        stack.push((
+                            path.clone(),
+                            state.after_guess(guess, clue),
+                        ))
+                    }
+                })
         }
+
+        final_paths
+    }
+
+    #[test]
+    fn test_results_match_serial() {
+        let wordle = GameState::<5>::from_wordle();
+        let words = wordle.possible_secrets()[..200].to_vec();
+        let state = GameState::new(words.clone(), words);
+
+        assert_eq!(
+            MiniMax.deterministic_strategy_results(state.clone()),
+            serial_results(&MiniMax, state)
+        );
     }
 }
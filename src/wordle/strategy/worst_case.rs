@@ -0,0 +1,227 @@
+use super::search::{self, StateKey};
+use super::Strategy;
+use crate::{Clue, DecisionTree, Error, GameState, Word};
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use itertools::Itertools;
+
+// Make whichever guess minimizes the number of guesses needed in the
+// worst case, against an adversary that may pick any clue consistent
+// with the remaining secrets.  Uses iterative deepening, so the first
+// guess found that solves every secret within `d` guesses is optimal.
+//
+// Setting `max_candidates` limits each node to the guesses with the
+// smallest worst-case partition.  The result is then only an upper
+// bound on the optimum.
+pub struct OptimalWorstCase<const N: usize> {
+    max_depth: usize,
+    max_candidates: Option<usize>,
+
+    // The smallest number of guesses known to solve a state, along
+    // with the guess that does so.
+    solutions: Mutex<HashMap<StateKey<N>, (usize, Word<N>)>>,
+
+    // The largest number of guesses known to be insufficient to solve
+    // a state.
+    insufficient: Mutex<HashMap<StateKey<N>, usize>>,
+}
+
+impl<const N: usize> OptimalWorstCase<N> {
+    pub fn new() -> Self {
+        Self {
+            max_depth: 6,
+            max_candidates: None,
+            solutions: Mutex::new(HashMap::new()),
+            insufficient: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // The maximum number of guesses searched.
+    pub fn with_max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    // The number of guesses searched at each node, ordered by the
+    // size of their largest partition.
+    pub fn with_max_candidates(self, max_candidates: usize) -> Self {
+        Self {
+            max_candidates: Some(max_candidates),
+            ..self
+        }
+    }
+
+    // Returns the number of guesses needed to solve every possible
+    // secret, against an adversary.  Returns None if it cannot be done
+    // within the depth limit.
+    pub fn worst_case(&self, state: &GameState<N>) -> Option<usize> {
+        self.optimum(state).map(|(depth, _guess)| depth)
+    }
+
+    // Returns a decision tree that solves every possible secret within
+    // `worst_case` guesses.  If `max_candidates` is unset, the search
+    // found no shallower tree, so the tree proves the optimum.
+    // Otherwise, it only proves an upper bound.
+    pub fn proof_tree(
+        &self,
+        state: &GameState<N>,
+    ) -> Result<DecisionTree<N>, Error> {
+        self.optimum(state).ok_or(Error::NoSolutionWithinDepth)?;
+        self.decision_tree(state)
+    }
+
+    // The largest number of secrets that could possibly be solved
+    // within `depth` guesses.  A guess may solve one secret directly,
+    // and split the others by at most every other clue.
+    fn max_solvable(depth: usize) -> usize {
+        (1..depth).fold(depth.min(1), |solvable, _| {
+            (Clue::<N>::num_clues() - 1)
+                .saturating_mul(solvable)
+                .saturating_add(1)
+        })
+    }
+
+    // Returns the smallest number of guesses that solves every secret,
+    // along with the guess to make.
+    fn optimum(&self, state: &GameState<N>) -> Option<(usize, Word<N>)> {
+        let num_secrets = state.num_possible_secrets();
+        let min_depth = (1..=self.max_depth)
+            .find(|&depth| Self::max_solvable(depth) >= num_secrets)?;
+        (min_depth..=self.max_depth).find_map(|depth| {
+            self.solve(state, depth).map(|guess| (depth, guess))
+        })
+    }
+
+    // Returns a guess that solves every secret within `depth` guesses,
+    // if one exists.
+    fn solve(&self, state: &GameState<N>, depth: usize) -> Option<Word<N>> {
+        let num_secrets = state.num_possible_secrets();
+        if num_secrets == 0 || num_secrets > Self::max_solvable(depth) {
+            return None;
+        } else if num_secrets == 1 {
+            return state.possible_secrets().first().copied();
+        }
+
+        let key = search::state_key(state);
+        if let Some(&(solved_depth, guess)) =
+            self.solutions.lock().unwrap().get(&key)
+        {
+            if solved_depth <= depth {
+                return Some(guess);
+            }
+        }
+        if let Some(&insufficient) = self.insufficient.lock().unwrap().get(&key)
+        {
+            if insufficient >= depth {
+                return None;
+            }
+        }
+
+        let child_limit = Self::max_solvable(depth - 1);
+        let solution = self
+            .candidates(state)
+            .into_iter()
+            .take_while(|((largest, _), _guess)| *largest <= child_limit)
+            .map(|(_score, guess)| guess)
+            .find(|guess| {
+                state
+                    .partition(guess)
+                    .into_iter()
+                    .filter(|(clue, _child)| !clue.all_correct())
+                    .map(|(_clue, child)| child)
+                    .sorted_by_key(|child| {
+                        std::cmp::Reverse(child.num_possible_secrets())
+                    })
+                    .all(|child| self.solve(&child, depth - 1).is_some())
+            });
+
+        match solution {
+            Some(guess) => {
+                let mut solutions = self.solutions.lock().unwrap();
+                let entry = solutions.entry(key).or_insert((depth, guess));
+                if depth < entry.0 {
+                    *entry = (depth, guess);
+                }
+            }
+            None => {
+                let mut insufficient = self.insufficient.lock().unwrap();
+                let entry = insufficient.entry(key).or_insert(depth);
+                *entry = (*entry).max(depth);
+            }
+        }
+
+        solution
+    }
+
+    // Returns the guesses worth searching, ordered by the size of
+    // their largest partition other than the solved secret, then
+    // preferring guesses that may be the secret.
+    fn candidates(
+        &self,
+        state: &GameState<N>,
+    ) -> Vec<((usize, bool), Word<N>)> {
+        search::candidates(state, self.max_candidates, |candidate| {
+            let largest = candidate
+                .clue_counts
+                .iter()
+                .filter(|(&id, _count)| id != 0)
+                .map(|(_id, &count)| count)
+                .max()
+                .unwrap_or(0);
+            (largest, !candidate.is_possible_secret)
+        })
+    }
+}
+
+impl<const N: usize> Default for OptimalWorstCase<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Strategy<N> for OptimalWorstCase<N> {
+    fn make_guess(&self, state: &GameState<N>) -> Result<Word<N>, Error> {
+        if state.num_possible_secrets() <= 1 {
+            return state
+                .possible_secrets()
+                .first()
+                .copied()
+                .ok_or(Error::NoWordsRemaining);
+        }
+        self.optimum(state)
+            .map(|(_depth, guess)| guess)
+            .ok_or(Error::NoSolutionWithinDepth)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::all_strategies;
+
+    #[test]
+    fn test_worst_case_beats_heuristics() -> Result<(), Error> {
+        let wordle = GameState::<5>::from_wordle();
+        let words = wordle.possible_secrets()[..100].to_vec();
+        let state = GameState::new(words.clone(), words).with_clue_table();
+
+        let solver = OptimalWorstCase::new();
+        let worst_case = solver.worst_case(&state).unwrap();
+        let tree = solver.proof_tree(&state)?;
+        tree.verify(&state)?;
+        assert_eq!(tree.depth(), worst_case);
+
+        let shallower = OptimalWorstCase::new().with_max_depth(worst_case - 1);
+        assert_eq!(shallower.worst_case(&state), None);
+
+        all_strategies::<5>().into_iter().try_for_each(
+            |(name, strategy)| -> Result<(), Error> {
+                let depth = strategy.decision_tree(&state)?.depth();
+                assert!(worst_case <= depth, "{} beat optimal", name);
+                Ok(())
+            },
+        )?;
+        Ok(())
+    }
+}
@@ -1,9 +1,11 @@
 use super::*;
 
-use std::convert::{TryFrom, TryInto};
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
+use itertools::Itertools;
+
 impl<const N: usize> Display for Word<N> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
         self.letters
@@ -15,14 +17,7 @@ impl<const N: usize> Display for Word<N> {
 
 impl<const N: usize> Display for Clue<N> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-        self.tiles
-            .iter()
-            .map(|t| match t {
-                Tile::Correct => 'G',
-                Tile::WrongPosition => 'Y',
-                Tile::NotPresentInWord => '_',
-            })
-            .try_for_each(|c| write!(f, "{}", c))
+        write!(f, "{}", self.display_in(ClueNotation::Letters))
     }
 }
 
@@ -52,29 +47,91 @@ impl<const N: usize> FromStr for Word<N> {
     }
 }
 
+// Accepts a tile written in any `ClueNotation`.
 impl TryFrom<char> for Tile {
     type Error = Error;
     fn try_from(c: char) -> Result<Self, Error> {
-        match c {
-            'G' => Ok(Tile::Correct),
-            'Y' => Ok(Tile::WrongPosition),
-            '_' => Ok(Tile::NotPresentInWord),
-            _ => Err(Error::NotTileChar(c)),
-        }
+        ClueNotation::ALL
+            .iter()
+            .find_map(|notation| notation.parse_tile(c))
+            .ok_or(Error::NotTileChar(c))
     }
 }
 
 impl<const N: usize> FromStr for Clue<N> {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Error> {
-        let vec_tiles: Vec<Tile> = s
-            .chars()
-            .map(|c| -> Result<Tile, Error> { c.try_into() })
-            .collect::<Result<Vec<Tile>, Error>>()?;
-        let tiles: [Tile; N] = vec_tiles
-            .as_slice()
-            .try_into()
-            .map_err(|_| Error::IncorrectStringLength)?;
-        Ok(Self { tiles })
+        notation::parse_clue_with(s, |c| Tile::try_from(c).ok())
+    }
+}
+
+// Decision trees are written in the format used by published wordle
+// tree listings, with one line per secret.  Each line alternates
+// between a lowercase guess and the clue received, with clues written
+// as B/Y/G, and ends with the secret and an all-correct clue.
+impl<const N: usize> Display for DecisionTree<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        self.secret_paths().iter().try_for_each(|(_secret, path)| {
+            let line = path
+                .iter()
+                .map(|(guess, clue)| {
+                    let clue: String = clue
+                        .iter()
+                        .map(|tile| match tile {
+                            Tile::Correct => 'G',
+                            Tile::WrongPosition => 'Y',
+                            Tile::NotPresentInWord => 'B',
+                        })
+                        .collect();
+                    format!("{} {}", guess.to_string().to_lowercase(), clue)
+                })
+                .join(" ");
+            writeln!(f, "{}", line)
+        })
+    }
+}
+
+impl<const N: usize> FromStr for DecisionTree<N> {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut root: Option<DecisionTree<N>> = None;
+
+        s.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .try_for_each(|line| -> Result<(), Error> {
+                let tokens = line.split_whitespace().collect::<Vec<_>>();
+                if tokens.len() % 2 != 0 {
+                    return Err(Error::InvalidDecisionTree(format!(
+                        "Line does not alternate guesses and clues: {}",
+                        line
+                    )));
+                }
+                let path = tokens
+                    .chunks(2)
+                    .map(|pair| -> Result<(Word<N>, Clue<N>), Error> {
+                        Ok((
+                            pair[0].parse()?,
+                            ClueNotation::Letters.parse_clue(pair[1])?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                if !path.last().is_some_and(|(_, clue)| clue.all_correct()) {
+                    return Err(Error::InvalidDecisionTree(format!(
+                        "Line does not end with a solved secret: {}",
+                        line
+                    )));
+                }
+
+                let first_guess = path[0].0;
+                let node = root.get_or_insert_with(|| {
+                    DecisionTree::new_leafless(first_guess)
+                });
+                node.insert_path(&path)
+            })?;
+
+        root.ok_or_else(|| {
+            Error::InvalidDecisionTree("No secrets listed".to_string())
+        })
     }
 }
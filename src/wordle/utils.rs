@@ -1,5 +1,6 @@
-use super::{Error, GameState, Word};
+use super::{DecisionTree, Error, GameState, Word};
 
+use std::collections::HashMap;
 use std::path::Path;
 
 impl<const N: usize> GameState<N> {
@@ -18,21 +19,45 @@ impl<const N: usize> GameState<N> {
             .filter(|s| s.len() == N)
             .collect_words();
 
-        Ok(Self {
-            made_correct_guess: false,
-            allowed_guesses,
-            possible_secrets,
-        })
+        Ok(Self::new(allowed_guesses, possible_secrets))
     }
 
     pub fn from_iter<'a>(word_iter: impl Iterator<Item = &'a str>) -> Self {
         let words: Vec<Word<N>> =
             word_iter.filter(|s| s.len() == N).collect_words();
-        Self {
-            made_correct_guess: false,
-            allowed_guesses: words.clone(),
-            possible_secrets: words,
-        }
+        Self::new(words.clone(), words)
+    }
+
+    // Load weights for the possible secrets from a file.  Each line
+    // contains a word and its frequency, separated by whitespace.
+    // Words of other lengths are ignored.  Frequencies must be finite
+    // and positive.
+    pub fn with_weights_from_file<P: AsRef<Path>>(
+        self,
+        path: &P,
+    ) -> Result<Self, Error> {
+        let weights = std::fs::read_to_string(path)
+            .map_err(Error::WordListReadError)?
+            .lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+            .filter(|tokens| !tokens.is_empty() && tokens[0].len() == N)
+            .map(|tokens| -> Result<(Word<N>, f64), Error> {
+                match tokens.as_slice() {
+                    [word, frequency] => {
+                        let frequency = frequency
+                            .parse::<f64>()
+                            .ok()
+                            .filter(|f| f.is_finite() && *f > 0.0)
+                            .ok_or_else(|| {
+                                Error::InvalidWeightFile(tokens.join(" "))
+                            })?;
+                        Ok((word.parse()?, frequency))
+                    }
+                    _ => Err(Error::InvalidWeightFile(tokens.join(" "))),
+                }
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(self.with_weights(&weights))
     }
 
     fn words_from_bytes(bytes: &[u8]) -> Vec<Word<N>> {
@@ -45,11 +70,7 @@ impl<const N: usize> GameState<N> {
 
     pub fn from_scrabble() -> Self {
         let words = Self::words_from_bytes(include_bytes!("scrabble.txt"));
-        Self {
-            made_correct_guess: false,
-            allowed_guesses: words.clone(),
-            possible_secrets: words,
-        }
+        Self::new(words.clone(), words)
     }
 
     pub fn from_wordle() -> Self {
@@ -63,11 +84,19 @@ impl<const N: usize> GameState<N> {
         .chain(possible_secrets.iter().cloned())
         .collect();
 
-        Self {
-            made_correct_guess: false,
-            allowed_guesses,
-            possible_secrets,
-        }
+        Self::new(allowed_guesses, possible_secrets)
+    }
+}
+
+impl<const N: usize> DecisionTree<N> {
+    pub fn from_file<P: AsRef<Path>>(path: &P) -> Result<Self, Error> {
+        std::fs::read_to_string(path)
+            .map_err(Error::TreeFileError)?
+            .parse()
+    }
+
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: &P) -> Result<(), Error> {
+        std::fs::write(path, self.to_string()).map_err(Error::TreeFileError)
     }
 }
 